@@ -1,6 +1,12 @@
+use std::collections::HashSet;
+use std::fmt;
 use std::str::FromStr;
 
-#[derive(Copy, Clone)]
+mod game;
+
+pub use game::{Game, GameEvent};
+
+#[derive(Copy, Clone, Debug)]
 pub struct BingoBox {
     value: u32,
     mark: Mark,
@@ -22,6 +28,19 @@ impl BingoBox {
     }
 }
 
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Cell {
+    pub row: usize,
+    pub column: usize,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum WinningPattern {
+    Row(usize),
+    Column(usize),
+}
+
+#[derive(Clone, Debug)]
 pub struct BingoBoard {
     pub boxes: [[BingoBox; 5]; 5],
 }
@@ -32,14 +51,22 @@ impl BingoBoard {
         BingoBoard { boxes }
     }
 
-    pub fn mark(&mut self, number: u32) {
-        for row_index in 0..5 {
-            for column_index in 0..5 {
-                if self.boxes[row_index][column_index].value == number {
-                    self.boxes[row_index][column_index].mark = Mark::Marked
+    pub fn mark(&mut self, number: u32) -> Vec<Cell> {
+        let mut marked_cells = Vec::new();
+        for row in 0..5 {
+            for column in 0..5 {
+                let bingo_box = &mut self.boxes[row][column];
+                if bingo_box.value == number && bingo_box.mark == Mark::Unmarked {
+                    bingo_box.mark = Mark::Marked;
+                    marked_cells.push(Cell { row, column });
                 }
             }
         }
+        marked_cells
+    }
+
+    pub fn unmark(&mut self, Cell { row, column }: Cell) {
+        self.boxes[row][column].mark = Mark::Unmarked
     }
 
     pub fn sum_unmarked_numbers(&self) -> u32 {
@@ -54,13 +81,17 @@ impl BingoBoard {
     }
 
     pub fn is_bingo(&self) -> bool {
+        self.winning_pattern().is_some()
+    }
+
+    pub fn winning_pattern(&self) -> Option<WinningPattern> {
         for row_index in 0..5 {
             if self
                 .row(row_index)
                 .iter()
                 .all(|bingo_box| bingo_box.mark == Mark::Marked)
             {
-                return true;
+                return Some(WinningPattern::Row(row_index));
             }
         }
         for column_index in 0..5 {
@@ -69,10 +100,10 @@ impl BingoBoard {
                 .iter()
                 .all(|bingo_box| bingo_box.mark == Mark::Marked)
             {
-                return true;
+                return Some(WinningPattern::Column(column_index));
             }
         }
-        false
+        None
     }
 
     fn row(&self, row_index: usize) -> Vec<BingoBox> {
@@ -84,26 +115,152 @@ impl BingoBoard {
     }
 }
 
+#[derive(Debug, Eq, PartialEq)]
+pub enum ParseBoardError {
+    WrongNumberOfRows { found: usize },
+    WrongNumberOfColumns { line: usize, found: usize },
+    InvalidNumber { line: usize, found: String },
+    DuplicateNumber { line: usize, number: u32 },
+}
+
+impl ParseBoardError {
+    pub fn line(&self) -> Option<usize> {
+        match self {
+            ParseBoardError::WrongNumberOfRows { .. } => None,
+            ParseBoardError::WrongNumberOfColumns { line, .. }
+            | ParseBoardError::InvalidNumber { line, .. }
+            | ParseBoardError::DuplicateNumber { line, .. } => Some(*line),
+        }
+    }
+}
+
+impl fmt::Display for ParseBoardError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseBoardError::WrongNumberOfRows { found } => {
+                write!(f, "expected 5 rows, found {}", found)
+            }
+            ParseBoardError::WrongNumberOfColumns { found, .. } => {
+                write!(f, "expected 5 numbers, found {}", found)
+            }
+            ParseBoardError::InvalidNumber { found, .. } => {
+                write!(f, "expected a number, found {:?}", found)
+            }
+            ParseBoardError::DuplicateNumber { number, .. } => {
+                write!(f, "expected unique numbers, found {} twice", number)
+            }
+        }
+    }
+}
+
 impl FromStr for BingoBoard {
-    type Err = ();
+    type Err = ParseBoardError;
     //    "22 13 17 11  0\n8  2 23  4 24\n21  9 14 16  7\n6 10  3 18  5\n1 12 20 15 19",
     fn from_str(board_str: &str) -> Result<Self, Self::Err> {
-        let numbers: Vec<u32> = board_str
-            .split_whitespace()
-            .filter_map(|b| b.parse::<u32>().ok())
+        let rows: Vec<(usize, &str)> = board_str
+            .lines()
+            .enumerate()
+            .map(|(index, row)| (index + 1, row))
+            .filter(|(_, row)| !row.trim().is_empty())
             .collect();
-        Ok(BingoBoard::new([
-            numbers[0..5].try_into().unwrap(),
-            numbers[5..10].try_into().unwrap(),
-            numbers[10..15].try_into().unwrap(),
-            numbers[15..20].try_into().unwrap(),
-            numbers[20..25].try_into().unwrap(),
-        ]))
+        if rows.len() != 5 {
+            return Err(ParseBoardError::WrongNumberOfRows { found: rows.len() });
+        }
+
+        let mut values = [[0; 5]; 5];
+        let mut seen = HashSet::new();
+        for (row_index, &(line, row)) in rows.iter().enumerate() {
+            let numbers = row
+                .split_whitespace()
+                .map(|n| {
+                    n.parse::<u32>()
+                        .map_err(|_| ParseBoardError::InvalidNumber {
+                            line,
+                            found: n.to_string(),
+                        })
+                })
+                .collect::<Result<Vec<u32>, _>>()?;
+            if numbers.len() != 5 {
+                return Err(ParseBoardError::WrongNumberOfColumns {
+                    line,
+                    found: numbers.len(),
+                });
+            }
+            for (column_index, number) in numbers.into_iter().enumerate() {
+                if !seen.insert(number) {
+                    return Err(ParseBoardError::DuplicateNumber { line, number });
+                }
+                values[row_index][column_index] = number;
+            }
+        }
+        Ok(BingoBoard::new(values))
     }
 }
 
-#[derive(Copy, Clone, Eq, PartialEq)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
 pub enum Mark {
     Marked,
     Unmarked,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_board() {
+        let board: BingoBoard =
+            "22 13 17 11  0\n 8  2 23  4 24\n21  9 14 16  7\n 6 10  3 18  5\n 1 12 20 15 19"
+                .parse()
+                .unwrap();
+        assert_eq!(board.boxes[1][0].value(), 8);
+        assert_eq!(board.boxes[4][4].value(), 19);
+    }
+
+    #[test]
+    fn reports_short_row() {
+        let result = "22 13 17 11  0\n 8  2 23  4\n21  9 14 16  7\n 6 10  3 18  5\n 1 12 20 15 19"
+            .parse::<BingoBoard>();
+        assert_eq!(
+            result.err(),
+            Some(ParseBoardError::WrongNumberOfColumns { line: 2, found: 4 })
+        );
+    }
+
+    #[test]
+    fn reports_missing_rows() {
+        let result = "22 13 17 11  0\n 8  2 23  4 24".parse::<BingoBoard>();
+        assert_eq!(
+            result.err(),
+            Some(ParseBoardError::WrongNumberOfRows { found: 2 })
+        );
+    }
+
+    #[test]
+    fn reports_invalid_number() {
+        let result =
+            "22 13 17 11  0\n 8  2 23  4 24\n21  9 x4 16  7\n 6 10  3 18  5\n 1 12 20 15 19"
+                .parse::<BingoBoard>();
+        assert_eq!(
+            result.err(),
+            Some(ParseBoardError::InvalidNumber {
+                line: 3,
+                found: String::from("x4")
+            })
+        );
+    }
+
+    #[test]
+    fn reports_duplicate_number() {
+        let result =
+            "22 13 17 11  0\n 8  2 23  4 24\n21  9 14 16  7\n 6 10  3 18  5\n 1 12 20 15 22"
+                .parse::<BingoBoard>();
+        assert_eq!(
+            result.err(),
+            Some(ParseBoardError::DuplicateNumber {
+                line: 5,
+                number: 22
+            })
+        );
+    }
+}
@@ -0,0 +1,166 @@
+use crate::domain::{BingoBoard, Cell, WinningPattern};
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum GameEvent {
+    Marked {
+        board: usize,
+        cell: Cell,
+    },
+    BoardWon {
+        board: usize,
+        pattern: WinningPattern,
+        score: u32,
+    },
+    GameOver,
+}
+
+pub struct Game {
+    boards: Vec<BingoBoard>,
+    winners: Vec<usize>,
+    draws: Vec<(u32, Vec<GameEvent>)>,
+}
+
+impl Game {
+    pub fn new(boards: Vec<BingoBoard>) -> Self {
+        Game {
+            boards,
+            winners: Vec::new(),
+            draws: Vec::new(),
+        }
+    }
+
+    pub fn draw(&mut self, number: u32) -> Vec<GameEvent> {
+        let mut events = Vec::new();
+        if !self.is_over() {
+            for (index, board) in self.boards.iter_mut().enumerate() {
+                if self.winners.contains(&index) {
+                    continue;
+                }
+                for cell in board.mark(number) {
+                    events.push(GameEvent::Marked { board: index, cell });
+                }
+                if let Some(pattern) = board.winning_pattern() {
+                    self.winners.push(index);
+                    events.push(GameEvent::BoardWon {
+                        board: index,
+                        pattern,
+                        score: board.sum_unmarked_numbers() * number,
+                    });
+                }
+            }
+            if self.is_over() {
+                events.push(GameEvent::GameOver);
+            }
+        }
+        self.draws.push((number, events.clone()));
+        events
+    }
+
+    pub fn undo(&mut self) -> Option<u32> {
+        let (number, events) = self.draws.pop()?;
+        for event in events.into_iter().rev() {
+            match event {
+                GameEvent::Marked { board, cell } => self.boards[board].unmark(cell),
+                GameEvent::BoardWon { board, .. } => self.winners.retain(|&w| w != board),
+                GameEvent::GameOver => {}
+            }
+        }
+        Some(number)
+    }
+
+    pub fn boards(&self) -> &[BingoBoard] {
+        &self.boards
+    }
+
+    pub fn board(&self, index: usize) -> Option<&BingoBoard> {
+        self.boards.get(index)
+    }
+
+    pub fn has_won(&self, index: usize) -> bool {
+        self.winners.contains(&index)
+    }
+
+    pub fn winners(&self) -> &[usize] {
+        &self.winners
+    }
+
+    pub fn drawn_numbers(&self) -> Vec<u32> {
+        self.draws.iter().map(|&(number, _)| number).collect()
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.winners.len() == self.boards.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::Mark;
+
+    fn board(offset: u32) -> BingoBoard {
+        let mut values = [[0; 5]; 5];
+        for (row, row_values) in values.iter_mut().enumerate() {
+            for (column, value) in row_values.iter_mut().enumerate() {
+                *value = offset + (row * 5 + column) as u32;
+            }
+        }
+        BingoBoard::new(values)
+    }
+
+    #[test]
+    fn drawing_marks_cells_on_every_board() {
+        let mut game = Game::new(vec![board(0), board(3)]);
+        let events = game.draw(4);
+        assert_eq!(
+            events,
+            vec![
+                GameEvent::Marked {
+                    board: 0,
+                    cell: Cell { row: 0, column: 4 }
+                },
+                GameEvent::Marked {
+                    board: 1,
+                    cell: Cell { row: 0, column: 1 }
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn completing_a_column_wins_and_ends_the_game() {
+        let mut game = Game::new(vec![board(0)]);
+        for number in [1, 6, 11, 16] {
+            game.draw(number);
+        }
+        let events = game.draw(21);
+        assert_eq!(
+            events[1..],
+            [
+                GameEvent::BoardWon {
+                    board: 0,
+                    pattern: WinningPattern::Column(1),
+                    score: (300 - 55) * 21,
+                },
+                GameEvent::GameOver,
+            ]
+        );
+        assert!(game.is_over());
+        assert!(game.draw(2).is_empty());
+    }
+
+    #[test]
+    fn undo_reverts_the_last_draw() {
+        let mut game = Game::new(vec![board(0)]);
+        for number in [0, 1, 2, 3, 4] {
+            game.draw(number);
+        }
+        assert!(game.has_won(0));
+
+        assert_eq!(game.undo(), Some(4));
+        assert!(!game.has_won(0));
+        assert_eq!(game.drawn_numbers(), vec![0, 1, 2, 3]);
+        assert!(game.board(0).unwrap().boxes[0][4].mark() == &Mark::Unmarked);
+        assert!(game.board(0).unwrap().boxes[0][3].mark() == &Mark::Marked);
+    }
+}
@@ -1,51 +1,125 @@
 mod domain;
-use domain::BingoBoard;
+mod render;
+mod simulation;
+use std::fmt;
 
-pub fn calculate_winning_score(contents: &str) -> u32 {
-    let (drawn_numbers, mut boards) = parse_input(contents);
+pub use domain::BingoBoard;
+pub use render::{render_board, render_boards, Style};
+pub use simulation::{generate_card, generate_draw_order, simulate, CardStatistics, Random};
 
-    for drawn_number in drawn_numbers {
-        for board in &mut boards {
-            board.mark(drawn_number);
-            if board.is_bingo() {
-                return board.sum_unmarked_numbers() * drawn_number;
+pub use domain::{BingoBox, Cell, Game, GameEvent, Mark, ParseBoardError, WinningPattern};
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum ParseBingoError {
+    MissingDrawnNumbers,
+    InvalidDrawnNumber {
+        position: usize,
+        found: String,
+    },
+    MissingBoards,
+    InvalidBoard {
+        board: usize,
+        line: usize,
+        error: ParseBoardError,
+    },
+}
+
+impl fmt::Display for ParseBingoError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseBingoError::MissingDrawnNumbers => {
+                write!(f, "line 1: expected drawn numbers, found nothing")
+            }
+            ParseBingoError::InvalidDrawnNumber { position, found } => write!(
+                f,
+                "line 1: expected a number as drawn number {}, found {:?}",
+                position, found
+            ),
+            ParseBingoError::MissingBoards => {
+                write!(f, "expected at least one board, found none")
+            }
+            ParseBingoError::InvalidBoard { board, line, error } => {
+                write!(f, "board {} (line {}): {}", board, line, error)
             }
         }
     }
+}
 
-    0
+pub fn calculate_winning_score(contents: &str) -> Result<u32, ParseBingoError> {
+    Ok(winning_scores(contents)?.next().unwrap_or(0))
 }
 
-pub fn calculate_losing_score(contents: &str) -> u32 {
-    let (drawn_numbers, mut boards) = parse_input(contents);
-    let mut winning_scores: Vec<u32> = Vec::new();
-    for drawn_number in drawn_numbers {
-        for board in &mut boards {
-            if board.is_bingo() {
-                continue;
-            }
+pub fn calculate_losing_score(contents: &str) -> Result<u32, ParseBingoError> {
+    Ok(winning_scores(contents)?.last().unwrap_or(0))
+}
 
-            board.mark(drawn_number);
+fn winning_scores(contents: &str) -> Result<impl Iterator<Item = u32>, ParseBingoError> {
+    let (drawn_numbers, boards) = parse_input(contents)?;
+    let mut game = Game::new(boards);
+    Ok(drawn_numbers
+        .into_iter()
+        .flat_map(move |drawn_number| game.draw(drawn_number))
+        .filter_map(|event| match event {
+            GameEvent::BoardWon { score, .. } => Some(score),
+            _ => None,
+        }))
+}
 
-            if board.is_bingo() {
-                winning_scores.push(board.sum_unmarked_numbers() * drawn_number);
-            }
+pub fn parse_input(contents: &str) -> Result<(Vec<u32>, Vec<BingoBoard>), ParseBingoError> {
+    let mut lines = contents
+        .lines()
+        .enumerate()
+        .map(|(index, line)| (index + 1, line));
+    let drawn_numbers = match lines.next() {
+        Some((_, line)) if !line.trim().is_empty() => parse_drawn_numbers(line)?,
+        _ => return Err(ParseBingoError::MissingDrawnNumbers),
+    };
+
+    let mut board_lines: Vec<(usize, Vec<&str>)> = Vec::new();
+    let mut previous_was_blank = true;
+    for (line_number, line) in lines {
+        if line.trim().is_empty() {
+            previous_was_blank = true;
+            continue;
         }
+        match board_lines.last_mut() {
+            Some((_, rows)) if !previous_was_blank => rows.push(line),
+            _ => board_lines.push((line_number, vec![line])),
+        }
+        previous_was_blank = false;
+    }
+    if board_lines.is_empty() {
+        return Err(ParseBingoError::MissingBoards);
     }
-    winning_scores.pop().unwrap_or(0)
+
+    let boards = board_lines
+        .into_iter()
+        .enumerate()
+        .map(|(index, (first_line, rows))| {
+            rows.join("\n")
+                .parse::<BingoBoard>()
+                .map_err(|error| ParseBingoError::InvalidBoard {
+                    board: index + 1,
+                    line: first_line + error.line().unwrap_or(1) - 1,
+                    error,
+                })
+        })
+        .collect::<Result<Vec<BingoBoard>, _>>()?;
+    Ok((drawn_numbers, boards))
 }
 
-fn parse_input(contents: &str) -> (Vec<u32>, Vec<BingoBoard>) {
-    let game: Vec<&str> = contents.splitn(2, "\n\n").collect();
-    let drawn_numbers: Vec<u32> = game[0]
-        .split(',')
-        .filter_map(|n| n.parse::<u32>().ok())
-        .collect();
-    let boards: Vec<BingoBoard> = game[1]
-        .split("\n\n")
-        .filter_map(|s| s.parse::<BingoBoard>().ok())
-        .collect();
-    (drawn_numbers, boards)
+fn parse_drawn_numbers(line: &str) -> Result<Vec<u32>, ParseBingoError> {
+    line.split(',')
+        .enumerate()
+        .map(|(index, n)| {
+            n.trim()
+                .parse::<u32>()
+                .map_err(|_| ParseBingoError::InvalidDrawnNumber {
+                    position: index + 1,
+                    found: n.trim().to_string(),
+                })
+        })
+        .collect()
 }
 
 #[cfg(test)]
@@ -74,7 +148,7 @@ mod tests {
             22 11 13  6  5\n\
              2  0 12  3  7\n";
         let result = calculate_winning_score(contents);
-        assert_eq!(result, 4512)
+        assert_eq!(result, Ok(4512))
     }
 
     #[test]
@@ -99,6 +173,54 @@ mod tests {
             22 11 13  6  5\n\
              2  0 12  3  7\n";
         let result = calculate_losing_score(contents);
-        assert_eq!(result, 1924)
+        assert_eq!(result, Ok(1924))
+    }
+
+    #[test]
+    fn reports_missing_boards() {
+        let result = calculate_winning_score("7,4,9,5,11\n\n");
+        assert_eq!(result, Err(ParseBingoError::MissingBoards))
+    }
+
+    #[test]
+    fn reports_invalid_drawn_number() {
+        let result = parse_input("7,4,x,5,11\n\n1 2 3 4 5\n");
+        assert_eq!(
+            result.err(),
+            Some(ParseBingoError::InvalidDrawnNumber {
+                position: 3,
+                found: String::from("x")
+            })
+        )
+    }
+
+    #[test]
+    fn reports_board_and_line_of_invalid_board() {
+        let contents = "7,4,9\n\
+            \n\
+            22 13 17 11  0\n\
+             8  2 23  4 24\n\
+            21  9 14 16  7\n\
+             6 10  3 18  5\n\
+             1 12 20 15 19\n\
+            \n\
+             3 15  0  2 22\n\
+             9 18 13 17  5\n\
+            19  8  7 25\n\
+            20 11 10 24  4\n\
+            14 21 16 12  6\n";
+        let error = parse_input(contents).err().unwrap();
+        assert_eq!(
+            error,
+            ParseBingoError::InvalidBoard {
+                board: 2,
+                line: 11,
+                error: ParseBoardError::WrongNumberOfColumns { line: 3, found: 4 }
+            }
+        );
+        assert_eq!(
+            error.to_string(),
+            "board 2 (line 11): expected 5 numbers, found 4"
+        );
     }
 }
@@ -1,12 +1,28 @@
-use day4::{calculate_losing_score, calculate_winning_score};
+use day4::{
+    calculate_losing_score, calculate_winning_score, parse_input, render_boards, Game, GameEvent,
+    ParseBingoError, Style,
+};
+use std::io::IsTerminal;
 use std::process::exit;
-use std::{env, fs};
+use std::{env, fs, io};
 
 fn main() {
-    match parse_arguments(env::args()) {
-        Ok(contents) => {
-            println!("Winning score: {}", calculate_winning_score(&contents));
-            println!("Winning score: {}", calculate_losing_score(&contents));
+    let result = parse_arguments(env::args()).and_then(|(contents, show)| {
+        if show {
+            show_game(&contents)?;
+        }
+        let winning_score = calculate_winning_score(&contents).map_err(Error::CannotParse)?;
+        let losing_score = calculate_losing_score(&contents).map_err(Error::CannotParse)?;
+        Ok((winning_score, losing_score))
+    });
+    match result {
+        Ok((winning_score, losing_score)) => {
+            println!("Winning score: {}", winning_score);
+            println!("Winning score: {}", losing_score);
+        }
+        Err(Error::CannotParse(error)) => {
+            eprintln!("Error: {}", error);
+            exit(1);
         }
         Err(error) => {
             eprintln!("Error: {:?}", error);
@@ -15,15 +31,46 @@ fn main() {
     };
 }
 
+fn show_game(contents: &str) -> Result<(), Error> {
+    let (drawn_numbers, boards) = parse_input(contents).map_err(Error::CannotParse)?;
+    let style = match io::stdout().is_terminal() {
+        true => Style::Ansi,
+        false => Style::Brackets,
+    };
+    let mut game = Game::new(boards);
+    for drawn_number in drawn_numbers {
+        let events = game.draw(drawn_number);
+        println!("Drawn: {}", drawn_number);
+        println!("{}", render_boards(game.boards(), style));
+        for event in events {
+            match event {
+                GameEvent::BoardWon { board, score, .. } => {
+                    println!("Board {} wins with score {}", board + 1, score)
+                }
+                GameEvent::GameOver => println!("Game over"),
+                GameEvent::Marked { .. } => {}
+            }
+        }
+        println!();
+        if game.is_over() {
+            break;
+        }
+    }
+    Ok(())
+}
+
 #[derive(Debug)]
 enum Error {
     NoFilename,
     CannotRead,
+    CannotParse(ParseBingoError),
 }
 
-fn parse_arguments(mut args: env::Args) -> Result<String, Error> {
-    args.next();
-    let filename = args.next().ok_or(Error::NoFilename)?;
+fn parse_arguments(args: env::Args) -> Result<(String, bool), Error> {
+    let (flags, mut args): (Vec<String>, Vec<String>) =
+        args.skip(1).partition(|arg| arg.starts_with("--"));
+    let show = flags.iter().any(|flag| flag == "--show");
+    let filename = args.drain(..).next().ok_or(Error::NoFilename)?;
     let contents = fs::read_to_string(&filename).ok();
-    contents.ok_or(Error::CannotRead)
+    Ok((contents.ok_or(Error::CannotRead)?, show))
 }
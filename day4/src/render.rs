@@ -0,0 +1,184 @@
+use crate::domain::{BingoBoard, BingoBox, Cell, Mark, WinningPattern};
+use std::fmt;
+
+const BOARD_SEPARATOR: &str = "   ";
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Style {
+    Brackets,
+    Ansi,
+}
+
+enum Highlight {
+    None,
+    Marked,
+    WinningLine,
+}
+
+pub fn render_board(board: &BingoBoard, style: Style) -> Vec<String> {
+    render_board_with_width(board, style, number_width(std::slice::from_ref(board)))
+}
+
+impl fmt::Display for BingoBoard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", render_board(self, Style::Brackets).join("\n"))
+    }
+}
+
+impl fmt::Display for BingoBox {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let highlight = match self.mark() {
+            Mark::Marked => Highlight::Marked,
+            Mark::Unmarked => Highlight::None,
+        };
+        let width = self.value().to_string().len();
+        write!(
+            f,
+            "{}",
+            render_number(self.value(), width, highlight, Style::Brackets)
+        )
+    }
+}
+
+pub fn render_boards(boards: &[BingoBoard], style: Style) -> String {
+    let width = number_width(boards);
+    let rendered: Vec<Vec<String>> = boards
+        .iter()
+        .enumerate()
+        .map(|(index, board)| {
+            let mut lines = render_board_with_width(board, style, width);
+            let board_width = 5 * (width + 2);
+            let title = match board.is_bingo() {
+                true => format!("Board {} BINGO", index + 1),
+                false => format!("Board {}", index + 1),
+            };
+            lines.insert(0, format!("{:<board_width$}", title));
+            lines
+        })
+        .collect();
+
+    (0..6)
+        .map(|line| {
+            rendered
+                .iter()
+                .map(|board| board[line].as_str())
+                .collect::<Vec<&str>>()
+                .join(BOARD_SEPARATOR)
+                .trim_end()
+                .to_string()
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+fn render_board_with_width(board: &BingoBoard, style: Style, width: usize) -> Vec<String> {
+    let winning_pattern = board.winning_pattern();
+    board
+        .boxes
+        .iter()
+        .enumerate()
+        .map(|(row, boxes)| {
+            boxes
+                .iter()
+                .enumerate()
+                .map(|(column, bingo_box)| {
+                    let highlight = if in_line(winning_pattern, Cell { row, column }) {
+                        Highlight::WinningLine
+                    } else if bingo_box.mark() == &Mark::Marked {
+                        Highlight::Marked
+                    } else {
+                        Highlight::None
+                    };
+                    render_number(bingo_box.value(), width, highlight, style)
+                })
+                .collect::<String>()
+        })
+        .collect()
+}
+
+fn render_number(value: u32, width: usize, highlight: Highlight, style: Style) -> String {
+    match (style, highlight) {
+        (_, Highlight::None) => format!(" {:>width$} ", value),
+        (Style::Brackets, Highlight::Marked) => format!("[{:>width$}]", value),
+        (Style::Brackets, Highlight::WinningLine) => format!("<{:>width$}>", value),
+        (Style::Ansi, Highlight::Marked) => format!("\x1b[1;33m {:>width$} \x1b[0m", value),
+        (Style::Ansi, Highlight::WinningLine) => format!("\x1b[1;30;42m {:>width$} \x1b[0m", value),
+    }
+}
+
+fn in_line(pattern: Option<WinningPattern>, Cell { row, column }: Cell) -> bool {
+    match pattern {
+        Some(WinningPattern::Row(winning_row)) => row == winning_row,
+        Some(WinningPattern::Column(winning_column)) => column == winning_column,
+        None => false,
+    }
+}
+
+fn number_width(boards: &[BingoBoard]) -> usize {
+    boards
+        .iter()
+        .flat_map(|board| board.boxes.iter().flatten())
+        .map(|bingo_box| bingo_box.value().to_string().len())
+        .max()
+        .unwrap_or(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn board() -> BingoBoard {
+        "22 13 17 11  0\n 8  2 23  4 24\n21  9 14 16  7\n 6 10  3 18  5\n 1 12 20 15 19"
+            .parse()
+            .unwrap()
+    }
+
+    #[test]
+    fn renders_marked_numbers_in_brackets() {
+        let mut board = board();
+        board.mark(13);
+        board.mark(8);
+        assert_eq!(
+            render_board(&board, Style::Brackets),
+            vec![
+                " 22 [13] 17  11   0 ",
+                "[ 8]  2  23   4  24 ",
+                " 21   9  14  16   7 ",
+                "  6  10   3  18   5 ",
+                "  1  12  20  15  19 ",
+            ]
+        );
+    }
+
+    #[test]
+    fn renders_single_box() {
+        let mut board = board();
+        board.mark(13);
+        assert_eq!(board.boxes[0][1].to_string(), "[13]");
+        assert_eq!(board.boxes[0][0].to_string(), " 22 ");
+    }
+
+    #[test]
+    fn renders_winning_line() {
+        let mut board = board();
+        for number in [17, 23, 14, 3, 20, 9] {
+            board.mark(number);
+        }
+        assert_eq!(
+            render_board(&board, Style::Brackets)[1],
+            "  8   2 <23>  4  24 "
+        );
+    }
+
+    #[test]
+    fn renders_boards_side_by_side() {
+        let mut first = board();
+        let second = board();
+        first.mark(22);
+        let rendered = render_boards(&[first, second], Style::Brackets);
+        let lines: Vec<&str> = rendered.lines().collect();
+        assert_eq!(lines.len(), 6);
+        assert_eq!(lines[0], "Board 1                Board 2");
+        assert_eq!(lines[1], "[22] 13  17  11   0     22  13  17  11   0");
+    }
+}
@@ -0,0 +1,142 @@
+use crate::domain::{BingoBoard, Game, GameEvent};
+
+mod random;
+
+pub use random::Random;
+
+pub fn generate_card(random: &mut Random, numbers: u32) -> Option<BingoBoard> {
+    if numbers < 25 {
+        return None;
+    }
+    let pool = generate_draw_order(random, numbers);
+    let mut values = [[0; 5]; 5];
+    for (index, &number) in pool.iter().take(25).enumerate() {
+        values[index / 5][index % 5] = number;
+    }
+    Some(BingoBoard::new(values))
+}
+
+pub fn generate_draw_order(random: &mut Random, numbers: u32) -> Vec<u32> {
+    let mut draw_order: Vec<u32> = (0..numbers).collect();
+    random.shuffle(&mut draw_order);
+    draw_order
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CardStatistics {
+    pub first_win_probability: f64,
+    // Averaged over the games the card won, None if it never won
+    pub expected_draws_to_win: Option<f64>,
+}
+
+// Boards that win on the same draw all count as winning first
+pub fn simulate(
+    cards: &[BingoBoard],
+    numbers: u32,
+    games: usize,
+    seed: u64,
+) -> Option<Vec<CardStatistics>> {
+    if games == 0 {
+        return None;
+    }
+    let mut random = Random::new(seed);
+    let mut first_wins = vec![0; cards.len()];
+    let mut wins = vec![0; cards.len()];
+    let mut total_draws = vec![0; cards.len()];
+
+    for _ in 0..games {
+        let mut game = Game::new(cards.to_vec());
+        let mut first_winning_draw = None;
+        for (draw_index, number) in generate_draw_order(&mut random, numbers)
+            .into_iter()
+            .enumerate()
+        {
+            for event in game.draw(number) {
+                if let GameEvent::BoardWon { board, .. } = event {
+                    wins[board] += 1;
+                    total_draws[board] += draw_index + 1;
+                    if *first_winning_draw.get_or_insert(draw_index) == draw_index {
+                        first_wins[board] += 1;
+                    }
+                }
+            }
+            if game.is_over() {
+                break;
+            }
+        }
+    }
+
+    let statistics = (0..cards.len())
+        .map(|card| CardStatistics {
+            first_win_probability: first_wins[card] as f64 / games as f64,
+            expected_draws_to_win: (wins[card] > 0)
+                .then(|| total_draws[card] as f64 / wins[card] as f64),
+        })
+        .collect();
+    Some(statistics)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn generated_card_has_unique_numbers_in_range() {
+        let card = generate_card(&mut Random::new(1), 30).unwrap();
+        let values: HashSet<u32> = card.boxes.iter().flatten().map(|b| b.value()).collect();
+        assert_eq!(values.len(), 25);
+        assert!(values.iter().all(|&value| value < 30));
+    }
+
+    #[test]
+    fn cannot_generate_card_from_too_few_numbers() {
+        assert!(generate_card(&mut Random::new(1), 24).is_none());
+    }
+
+    #[test]
+    fn simulation_is_reproducible() {
+        let mut random = Random::new(3);
+        let cards: Vec<BingoBoard> = (0..4)
+            .map(|_| generate_card(&mut random, 100).unwrap())
+            .collect();
+        assert_eq!(
+            simulate(&cards, 100, 200, 11),
+            simulate(&cards, 100, 200, 11)
+        );
+    }
+
+    #[test]
+    fn identical_cards_always_win_together() {
+        let card = generate_card(&mut Random::new(5), 50).unwrap();
+        let statistics = simulate(&[card.clone(), card], 50, 100, 9).unwrap();
+        assert_eq!(statistics[0], statistics[1]);
+        assert_eq!(statistics[0].first_win_probability, 1.0);
+        assert!(statistics[0].expected_draws_to_win.unwrap() >= 5.0);
+    }
+
+    #[test]
+    fn no_games_means_no_statistics() {
+        let card = generate_card(&mut Random::new(5), 50).unwrap();
+        assert_eq!(simulate(&[card], 50, 0, 9), None);
+    }
+
+    #[test]
+    fn card_that_cannot_win_has_no_expected_draws() {
+        let mut values = [[0; 5]; 5];
+        for (index, value) in values.iter_mut().flatten().enumerate() {
+            *value = index as u32;
+        }
+        let reachable = BingoBoard::new(values);
+        // Every line of this card contains a number that is never drawn
+        for (index, row) in values.iter_mut().enumerate() {
+            row[index] += 100;
+        }
+        let unreachable = BingoBoard::new(values);
+        let statistics = simulate(&[reachable, unreachable], 25, 50, 4).unwrap();
+        let draws = statistics[0].expected_draws_to_win.unwrap();
+        assert!((5.0..=25.0).contains(&draws));
+        assert_eq!(statistics[1].first_win_probability, 0.0);
+        assert_eq!(statistics[1].expected_draws_to_win, None);
+    }
+}
@@ -1,28 +1,89 @@
+use std::collections::HashMap;
 use std::str::FromStr;
 
-#[derive(Eq, PartialEq, Debug, Ord, PartialOrd, Clone, Copy)]
-pub struct Point(pub usize, pub usize);
+mod image;
+mod overlap;
+
+pub use overlap::count_overlaps;
+
+// Past this many grid cells per drawn point a dense grid wastes more memory than a hash map
+const SPARSE_DENSITY_THRESHOLD: u64 = 16;
+
+pub type Coordinate = i64;
+
+#[derive(Eq, PartialEq, Debug, Ord, PartialOrd, Clone, Copy, Hash)]
+pub struct Point(pub Coordinate, pub Coordinate);
 
 impl FromStr for Point {
     type Err = ();
 
     fn from_str(str: &str) -> Result<Self, Self::Err> {
         let (x, y) = str.split_once(',').ok_or(())?;
-        let x: usize = x.parse().or(Err(()))?;
-        let y: usize = y.parse().or(Err(()))?;
-        return Ok(Point(x, y));
+        let x: Coordinate = x.parse().or(Err(()))?;
+        let y: Coordinate = y.parse().or(Err(()))?;
+        Ok(Point(x, y))
     }
 }
 
-fn range(a: usize, b: usize) -> Box<dyn Iterator<Item = usize>> {
-    if a < b {
-        Box::new(a..=b)
-    } else {
-        Box::new((b..=a).rev())
+fn gcd(a: i64, b: i64) -> i64 {
+    match b {
+        0 => a,
+        _ => gcd(b, a % b),
     }
 }
 
-#[derive(Eq, PartialEq, Debug)]
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum Orientation {
+    Horizontal,
+    Vertical,
+    Diagonal,
+    Oblique,
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum Rasterization {
+    Lattice,
+    Bresenham,
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum LineFilter {
+    AxisAligned,
+    Diagonal,
+    All,
+}
+
+impl LineFilter {
+    pub fn accepts(&self, line: &Line) -> bool {
+        matches!(
+            (self, line.orientation()),
+            (LineFilter::All, _)
+                | (
+                    LineFilter::AxisAligned,
+                    Orientation::Horizontal | Orientation::Vertical
+                )
+                | (
+                    LineFilter::Diagonal,
+                    Orientation::Diagonal | Orientation::Oblique
+                )
+        )
+    }
+}
+
+impl FromStr for LineFilter {
+    type Err = ();
+
+    fn from_str(str: &str) -> Result<Self, Self::Err> {
+        match str {
+            "axis" => Ok(LineFilter::AxisAligned),
+            "diagonal" => Ok(LineFilter::Diagonal),
+            "all" => Ok(LineFilter::All),
+            _ => Err(()),
+        }
+    }
+}
+
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
 pub struct Line {
     from: Point,
     to: Point,
@@ -33,21 +94,103 @@ impl Line {
         Line { from, to }
     }
 
-    fn points(&self) -> Vec<Point> {
+    pub fn from(&self) -> Point {
+        self.from
+    }
+
+    pub fn to(&self) -> Point {
+        self.to
+    }
+
+    pub fn orientation(&self) -> Orientation {
+        if self.from.1 == self.to.1 {
+            Orientation::Horizontal
+        } else if self.from.0 == self.to.0 {
+            Orientation::Vertical
+        } else if self.from.0.abs_diff(self.to.0) == self.from.1.abs_diff(self.to.1) {
+            Orientation::Diagonal
+        } else {
+            Orientation::Oblique
+        }
+    }
+
+    fn bounds(&self) -> (Point, Point) {
         let Point(x1, y1) = self.from;
         let Point(x2, y2) = self.to;
+        (Point(x1.min(x2), y1.min(y2)), Point(x1.max(x2), y1.max(y2)))
+    }
 
-        if y1 == y2 {
-            range(x1, x2).map(move |x| Point(x, y1)).collect()
-        } else if x1 == x2 {
-            range(y1, y2).map(move |y| Point(x1, y)).collect()
-        } else {
-            range(x1, x2)
-                .zip(range(y1, y2))
-                .map(|(x, y)| Point(x, y))
-                .collect()
+    fn number_of_points(&self, rasterization: Rasterization) -> usize {
+        let (dx, dy) = (
+            self.from.0.abs_diff(self.to.0),
+            self.from.1.abs_diff(self.to.1),
+        );
+        let steps = match rasterization {
+            Rasterization::Lattice => gcd(dx as i64, dy as i64) as u64,
+            Rasterization::Bresenham => dx.max(dy),
+        };
+        steps as usize + 1
+    }
+
+    pub fn passes_through(&self, point: Point, rasterization: Rasterization) -> bool {
+        let (min, max) = self.bounds();
+        if !(min.0..=max.0).contains(&point.0) || !(min.1..=max.1).contains(&point.1) {
+            return false;
+        }
+        match rasterization {
+            Rasterization::Lattice => {
+                let (dx, dy) = (self.to.0 - self.from.0, self.to.1 - self.from.1);
+                let (px, py) = (point.0 - self.from.0, point.1 - self.from.1);
+                dx as i128 * py as i128 == dy as i128 * px as i128
+            }
+            Rasterization::Bresenham => self.rasterize(rasterization).contains(&point),
+        }
+    }
+
+    pub fn points(&self) -> Vec<Point> {
+        self.rasterize(Rasterization::Lattice)
+    }
+
+    pub fn rasterize(&self, rasterization: Rasterization) -> Vec<Point> {
+        match rasterization {
+            Rasterization::Lattice => lattice_points(self.from, self.to),
+            Rasterization::Bresenham => bresenham_points(self.from, self.to),
+        }
+    }
+}
+
+// Only the points with integer coordinates that lie exactly on the line
+fn lattice_points(Point(x1, y1): Point, Point(x2, y2): Point) -> Vec<Point> {
+    let (dx, dy) = (x2 - x1, y2 - y1);
+    let steps = gcd(dx.abs(), dy.abs());
+    if steps == 0 {
+        return vec![Point(x1, y1)];
+    }
+    (0..=steps)
+        .map(|step| Point(x1 + step * dx / steps, y1 + step * dy / steps))
+        .collect()
+}
+
+// One point per step along the major axis, the closest to the line
+fn bresenham_points(Point(x1, y1): Point, Point(x2, y2): Point) -> Vec<Point> {
+    let (dx, dy) = ((x2 - x1).abs(), -(y2 - y1).abs());
+    let (step_x, step_y) = ((x2 - x1).signum(), (y2 - y1).signum());
+    let mut error = dx + dy;
+    let (mut x, mut y) = (x1, y1);
+    let mut points = vec![Point(x, y)];
+    while (x, y) != (x2, y2) {
+        let double_error = 2 * error;
+        if double_error >= dy {
+            error += dy;
+            x += step_x;
         }
+        if double_error <= dx {
+            error += dx;
+            y += step_y;
+        }
+        points.push(Point(x, y));
     }
+    points
 }
 
 impl FromStr for Line {
@@ -57,31 +200,251 @@ impl FromStr for Line {
         let (from, to) = line_str.split_once(" -> ").ok_or(())?;
         let from: Point = from.parse().or(Err(()))?;
         let to: Point = to.parse().or(Err(()))?;
-        return Ok(Line::new(from, to));
+        Ok(Line::new(from, to))
     }
 }
 
+#[derive(Debug)]
+enum Storage {
+    Dense {
+        origin: Point,
+        width: usize,
+        height: usize,
+        dots: Vec<u32>,
+    },
+    Sparse(HashMap<Point, u32>),
+}
+
+pub type LineId = usize;
+
 #[derive(Debug)]
 pub struct Board {
-    dots: Vec<Vec<u32>>,
+    storage: Storage,
+    lines: Vec<Option<(Line, Rasterization)>>,
+    intersections: usize,
 }
 
 impl Board {
     pub fn new() -> Self {
+        Board::sparse()
+    }
+
+    pub fn sparse() -> Self {
         Board {
-            dots: vec![vec![0; 1000]; 1000],
+            storage: Storage::Sparse(HashMap::new()),
+            lines: Vec::new(),
+            intersections: 0,
+        }
+    }
+
+    pub fn dense(min: Point, max: Point) -> Self {
+        let width = max.0.abs_diff(min.0) as usize + 1;
+        let height = max.1.abs_diff(min.1) as usize + 1;
+        Board {
+            storage: Storage::Dense {
+                origin: min,
+                width,
+                height,
+                dots: vec![0; width * height],
+            },
+            lines: Vec::new(),
+            intersections: 0,
+        }
+    }
+
+    pub fn for_lines(lines: &[Line], rasterization: Rasterization) -> Self {
+        let bounds = lines
+            .iter()
+            .map(Line::bounds)
+            .reduce(|(min1, max1), (min2, max2)| {
+                (
+                    Point(min1.0.min(min2.0), min1.1.min(min2.1)),
+                    Point(max1.0.max(max2.0), max1.1.max(max2.1)),
+                )
+            });
+        let (min, max) = match bounds {
+            Some(bounds) => bounds,
+            None => return Board::sparse(),
+        };
+        let number_of_points: usize = lines
+            .iter()
+            .map(|line| line.number_of_points(rasterization))
+            .sum();
+        let area = (max.0.abs_diff(min.0) + 1).saturating_mul(max.1.abs_diff(min.1) + 1);
+        if area / number_of_points as u64 > SPARSE_DENSITY_THRESHOLD {
+            Board::sparse()
+        } else {
+            Board::dense(min, max)
+        }
+    }
+
+    pub fn is_sparse(&self) -> bool {
+        matches!(self.storage, Storage::Sparse(_))
+    }
+
+    pub fn draw(&mut self, line: Line) -> LineId {
+        self.draw_with(line, Rasterization::Lattice)
+    }
+
+    pub fn draw_with(&mut self, line: Line, rasterization: Rasterization) -> LineId {
+        for point in line.rasterize(rasterization) {
+            self.increment(point);
         }
+        self.lines.push(Some((line, rasterization)));
+        self.lines.len() - 1
+    }
+
+    pub fn remove(&mut self, id: LineId) -> Option<Line> {
+        let (line, rasterization) = self.lines.get_mut(id)?.take()?;
+        for point in line.rasterize(rasterization) {
+            self.decrement(point);
+        }
+        Some(line)
+    }
+
+    pub fn line(&self, id: LineId) -> Option<&Line> {
+        self.lines.get(id)?.as_ref().map(|(line, _)| line)
+    }
+
+    pub fn lines_through(&self, point: Point) -> Vec<LineId> {
+        self.lines
+            .iter()
+            .enumerate()
+            .filter_map(|(id, line)| line.as_ref().map(|line| (id, line)))
+            .filter(|(_, (line, rasterization))| line.passes_through(point, *rasterization))
+            .map(|(id, _)| id)
+            .collect()
     }
 
-    pub fn draw(&mut self, line: Line) {
-        for point in line.points() {
-            let Point(x, y) = point;
-            self.dots[x][y] += 1;
+    fn increment(&mut self, point: Point) {
+        let count = match &mut self.storage {
+            Storage::Dense {
+                origin,
+                width,
+                height,
+                dots,
+            } => match dense_index(*origin, *width, *height, point) {
+                Some(index) => {
+                    dots[index] += 1;
+                    dots[index]
+                }
+                None => {
+                    self.storage = Storage::Sparse(self.dots().collect());
+                    return self.increment(point);
+                }
+            },
+            Storage::Sparse(dots) => {
+                let count = dots.entry(point).or_insert(0);
+                *count += 1;
+                *count
+            }
+        };
+        if count == 2 {
+            self.intersections += 1;
         }
     }
+
+    fn decrement(&mut self, point: Point) {
+        let count = match &mut self.storage {
+            Storage::Dense {
+                origin,
+                width,
+                height,
+                dots,
+            } => match dense_index(*origin, *width, *height, point) {
+                Some(index) => {
+                    dots[index] -= 1;
+                    dots[index]
+                }
+                None => return,
+            },
+            Storage::Sparse(dots) => match dots.get_mut(&point) {
+                Some(count) if *count > 1 => {
+                    *count -= 1;
+                    *count
+                }
+                Some(_) => {
+                    dots.remove(&point);
+                    0
+                }
+                None => return,
+            },
+        };
+        if count == 1 {
+            self.intersections -= 1;
+        }
+    }
+
+    pub fn count_at(&self, point: Point) -> u32 {
+        match &self.storage {
+            Storage::Dense {
+                origin,
+                width,
+                height,
+                dots,
+            } => dense_index(*origin, *width, *height, point).map_or(0, |index| dots[index]),
+            Storage::Sparse(dots) => dots.get(&point).copied().unwrap_or(0),
+        }
+    }
+
+    fn dots(&self) -> Box<dyn Iterator<Item = (Point, u32)> + '_> {
+        match &self.storage {
+            Storage::Dense {
+                origin,
+                width,
+                dots,
+                ..
+            } => Box::new(dots.iter().enumerate().filter(|(_, &f)| f > 0).map(
+                move |(index, &f)| {
+                    let (x, y) = (index % width, index / width);
+                    (
+                        Point(origin.0 + x as Coordinate, origin.1 + y as Coordinate),
+                        f,
+                    )
+                },
+            )),
+            Storage::Sparse(dots) => Box::new(dots.iter().map(|(&point, &f)| (point, f))),
+        }
+    }
+
     pub fn count_intersections(&self) -> usize {
-        self.dots.iter().flatten().filter(|&&f| f > 1).count()
+        self.intersections
     }
+
+    pub fn count_at_least(&self, overlap: u32) -> usize {
+        self.dots().filter(|&(_, f)| f >= overlap).count()
+    }
+
+    pub fn points_with_overlap(&self, overlap: u32) -> Vec<Point> {
+        let mut points: Vec<Point> = self
+            .dots()
+            .filter(|&(_, f)| f >= overlap)
+            .map(|(point, _)| point)
+            .collect();
+        points.sort_unstable();
+        points
+    }
+
+    pub fn count_intersections_in(&self, min: Point, max: Point, overlap: u32) -> usize {
+        self.dots()
+            .filter(|&(Point(x, y), f)| {
+                f >= overlap && (min.0..=max.0).contains(&x) && (min.1..=max.1).contains(&y)
+            })
+            .count()
+    }
+}
+
+impl Default for Board {
+    fn default() -> Self {
+        Board::new()
+    }
+}
+
+// Translates a point relative to the origin, so negative coordinates can be stored as well
+fn dense_index(origin: Point, width: usize, height: usize, Point(x, y): Point) -> Option<usize> {
+    let x = usize::try_from(x - origin.0).ok().filter(|&x| x < width)?;
+    let y = usize::try_from(y - origin.1).ok().filter(|&y| y < height)?;
+    Some(y * width + x)
 }
 
 #[cfg(test)]
@@ -116,12 +479,313 @@ mod tests {
         assert_eq!(result, line.points());
     }
 
+    #[test]
+    fn test_lattice_points_in_sloped_line() {
+        let line = Line::new(Point(0, 0), Point(6, 3));
+        let result = vec![Point(0, 0), Point(2, 1), Point(4, 2), Point(6, 3)];
+        assert_eq!(result, line.points());
+    }
+
+    #[test]
+    fn test_lattice_points_in_steep_line() {
+        let line = Line::new(Point(5, 7), Point(4, 0));
+        assert_eq!(vec![Point(5, 7), Point(4, 0)], line.points());
+    }
+
+    #[test]
+    fn test_bresenham_points_in_sloped_line() {
+        let line = Line::new(Point(0, 0), Point(6, 3));
+        let result = vec![
+            Point(0, 0),
+            Point(1, 1),
+            Point(2, 1),
+            Point(3, 2),
+            Point(4, 2),
+            Point(5, 3),
+            Point(6, 3),
+        ];
+        assert_eq!(result, line.rasterize(Rasterization::Bresenham));
+    }
+
+    #[test]
+    fn test_bresenham_matches_lattice_for_45_line() {
+        let line = Line::new(Point(3, 1), Point(1, 3));
+        assert_eq!(line.rasterize(Rasterization::Bresenham), line.points());
+    }
+
+    #[test]
+    fn test_orientation() {
+        assert_eq!(
+            Line::new(Point(9, 7), Point(7, 7)).orientation(),
+            Orientation::Horizontal
+        );
+        assert_eq!(
+            Line::new(Point(1, 1), Point(1, 3)).orientation(),
+            Orientation::Vertical
+        );
+        assert_eq!(
+            Line::new(Point(3, 1), Point(1, 3)).orientation(),
+            Orientation::Diagonal
+        );
+        assert_eq!(
+            Line::new(Point(0, 0), Point(6, 3)).orientation(),
+            Orientation::Oblique
+        );
+    }
+
+    #[test]
+    fn test_line_filter() {
+        let horizontal = Line::new(Point(9, 7), Point(7, 7));
+        let diagonal = Line::new(Point(3, 1), Point(1, 3));
+        assert!(LineFilter::AxisAligned.accepts(&horizontal));
+        assert!(!LineFilter::AxisAligned.accepts(&diagonal));
+        assert!(!LineFilter::Diagonal.accepts(&horizontal));
+        assert!(LineFilter::Diagonal.accepts(&diagonal));
+        assert!(LineFilter::All.accepts(&horizontal));
+        assert!(LineFilter::All.accepts(&diagonal));
+    }
+
     #[test]
     fn test_drawing() {
         let mut board = Board::new();
         board.draw(Line::new(Point(1, 1), Point(1, 3)));
         board.draw(Line::new(Point(1, 2), Point(1, 4)));
-        assert_eq!(board.dots[1][1], 1);
-        assert_eq!(board.dots[1][2], 2);
+        assert_eq!(board.count_at(Point(1, 1)), 1);
+        assert_eq!(board.count_at(Point(1, 2)), 2);
+    }
+
+    #[test]
+    fn test_dense_board_sized_to_lines() {
+        let lines = vec![
+            Line::new(Point(1000, 1000), Point(1000, 1003)),
+            Line::new(Point(1000, 1002), Point(1003, 1002)),
+        ];
+        let mut board = Board::for_lines(&lines, Rasterization::Lattice);
+        assert!(!board.is_sparse());
+        for line in lines {
+            board.draw(line);
+        }
+        assert_eq!(board.count_at(Point(1000, 1002)), 2);
+        assert_eq!(board.count_intersections(), 1);
+    }
+
+    #[test]
+    fn test_sparse_board_for_far_apart_lines() {
+        let lines = vec![
+            Line::new(Point(0, 0), Point(0, 3)),
+            Line::new(Point(5_000_000, 7_000_000), Point(5_000_000, 7_000_003)),
+        ];
+        let board = Board::for_lines(&lines, Rasterization::Lattice);
+        assert!(board.is_sparse());
+    }
+
+    #[test]
+    fn test_sparse_board_for_long_oblique_lines() {
+        let line = Line::new(Point(0, 0), Point(5_000_000_000, 1));
+        assert!(Board::for_lines(&[line, line], Rasterization::Lattice).is_sparse());
+        assert_eq!(line.number_of_points(Rasterization::Lattice), 2);
+        assert_eq!(
+            line.number_of_points(Rasterization::Bresenham),
+            5_000_000_001
+        );
+    }
+
+    #[test]
+    fn test_dense_board_switches_to_sparse_outside_bounds() {
+        let mut board = Board::dense(Point(0, 0), Point(3, 3));
+        board.draw(Line::new(Point(0, 0), Point(3, 3)));
+        board.draw(Line::new(Point(3, 3), Point(5, 3)));
+        assert!(board.is_sparse());
+        assert_eq!(board.count_at(Point(3, 3)), 2);
+        assert_eq!(board.count_at(Point(5, 3)), 1);
+        assert_eq!(board.count_intersections(), 1);
+    }
+
+    // Linear congruential generator, so the randomized tests are reproducible
+    fn pseudo_random(mut seed: u64) -> impl FnMut(Coordinate) -> Coordinate {
+        move |bound| {
+            seed = seed
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            (seed >> 33) as Coordinate % bound
+        }
+    }
+
+    #[test]
+    fn test_count_overlaps_matches_board() {
+        let mut random = pseudo_random(17);
+        let mut lines = Vec::new();
+        for _ in 0..200 {
+            let (x, y, length) = (random(60), random(60), random(40));
+            let line = match random(6) {
+                0 => Line::new(Point(x, y), Point(x + length, y)),
+                1 => Line::new(Point(x, y + length), Point(x, y)),
+                2 => Line::new(Point(x, y), Point(x + length, y + length)),
+                3 => Line::new(Point(x + length, y), Point(x, y + length)),
+                4 => Line::new(Point(x, y), Point(x + 2 * length, y + length / 2)),
+                _ => Line::new(Point(x, y), Point(x, y)),
+            };
+            lines.push(line);
+        }
+        let expected = count_overlaps(&lines);
+        let mut board = Board::sparse();
+        for line in lines {
+            board.draw(line);
+        }
+        assert_eq!(expected, board.count_intersections());
+    }
+
+    #[test]
+    fn test_count_overlaps_of_long_lines() {
+        let lines = vec![
+            Line::new(Point(0, 0), Point(5_000_000, 0)),
+            Line::new(Point(9_000_000, 0), Point(1_000_000, 0)),
+            Line::new(Point(2_000_000, 0), Point(2_000_000, 3_000_000)),
+            Line::new(Point(7_000_000, 0), Point(7_000_000, 10)),
+            Line::new(Point(0, 11), Point(1, 10)),
+            Line::new(Point(0, 10), Point(1, 11)),
+        ];
+        assert_eq!(count_overlaps(&lines), 4_000_002);
+    }
+
+    #[test]
+    fn test_parse_negative_point() {
+        assert_eq!("-3,12".parse(), Ok(Point(-3, 12)));
+        assert_eq!(
+            "-3,-4 -> 5,-4".parse(),
+            Ok(Line::new(Point(-3, -4), Point(5, -4)))
+        );
+    }
+
+    #[test]
+    fn test_drawing_negative_coordinates() {
+        let lines = vec![
+            Line::new(Point(-5, -5), Point(5, 5)),
+            Line::new(Point(-5, 5), Point(5, -5)),
+            Line::new(Point(-2, -2), Point(-2, 0)),
+        ];
+        let expected = count_overlaps(&lines);
+        let mut dense = Board::for_lines(&lines, Rasterization::Lattice);
+        let mut sparse = Board::sparse();
+        assert!(!dense.is_sparse());
+        for line in lines {
+            sparse.draw(Line::new(line.from(), line.to()));
+            dense.draw(line);
+        }
+        assert_eq!(dense.count_at(Point(-2, -2)), 2);
+        assert_eq!(dense.count_at(Point(0, 0)), 2);
+        assert_eq!(dense.count_intersections(), 2);
+        assert_eq!(sparse.count_intersections(), 2);
+        assert_eq!(expected, 2);
+    }
+
+    #[test]
+    fn test_passes_through() {
+        let line = Line::new(Point(0, 0), Point(6, 3));
+        assert!(line.passes_through(Point(4, 2), Rasterization::Lattice));
+        assert!(!line.passes_through(Point(3, 2), Rasterization::Lattice));
+        assert!(!line.passes_through(Point(8, 4), Rasterization::Lattice));
+        assert!(line.passes_through(Point(3, 2), Rasterization::Bresenham));
+    }
+
+    #[test]
+    fn test_spatial_queries() {
+        let mut board = Board::dense(Point(0, 0), Point(9, 9));
+        let horizontal = board.draw(Line::new(Point(0, 4), Point(9, 4)));
+        let vertical = board.draw(Line::new(Point(4, 0), Point(4, 9)));
+        let diagonal = board.draw(Line::new(Point(0, 0), Point(9, 9)));
+        board.draw(Line::new(Point(7, 0), Point(7, 2)));
+
+        assert_eq!(
+            board.lines_through(Point(4, 4)),
+            vec![horizontal, vertical, diagonal]
+        );
+        assert_eq!(board.lines_through(Point(7, 4)), vec![horizontal]);
+        assert_eq!(board.lines_through(Point(8, 0)), Vec::<LineId>::new());
+        assert_eq!(
+            board.line(vertical),
+            Some(&Line::new(Point(4, 0), Point(4, 9)))
+        );
+
+        assert_eq!(board.points_with_overlap(3), vec![Point(4, 4)]);
+        assert_eq!(board.count_at_least(2), 1);
+        assert_eq!(board.count_at_least(1), 10 + 9 + 9 + 3);
+        assert_eq!(board.count_intersections_in(Point(0, 0), Point(3, 3), 2), 0);
+        assert_eq!(board.count_intersections_in(Point(3, 3), Point(5, 5), 2), 1);
+        assert_eq!(board.count_intersections_in(Point(3, 3), Point(5, 5), 3), 1);
+        assert_eq!(board.count_intersections_in(Point(0, 0), Point(3, 3), 1), 4);
+    }
+
+    #[test]
+    fn test_removing_lines_keeps_count_up_to_date() {
+        let mut random = pseudo_random(23);
+        let mut board = Board::dense(Point(0, 0), Point(30, 30));
+        let mut drawn: Vec<(LineId, Line)> = Vec::new();
+        for _ in 0..300 {
+            if !drawn.is_empty() && random(3) == 0 {
+                let (id, line) = drawn.remove(random(drawn.len() as Coordinate) as usize);
+                assert_eq!(board.remove(id), Some(line));
+            } else {
+                let from = Point(random(40) - 5, random(40) - 5);
+                let to = match random(3) {
+                    0 => Point(from.0 + random(10), from.1),
+                    1 => Point(from.0, from.1 - random(10)),
+                    _ => Point(from.0 + 5, from.1 + 5),
+                };
+                let line = Line::new(from, to);
+                drawn.push((board.draw(line), line));
+            }
+
+            let mut recomputed = Board::sparse();
+            for &(_, line) in &drawn {
+                recomputed.draw(line);
+            }
+            assert_eq!(
+                board.count_intersections(),
+                recomputed.count_intersections()
+            );
+            assert_eq!(board.count_intersections(), board.count_at_least(2));
+        }
+    }
+
+    #[test]
+    fn test_removed_line_is_forgotten() {
+        let mut board = Board::sparse();
+        let first = board.draw(Line::new(Point(0, 0), Point(0, 3)));
+        let second = board.draw(Line::new(Point(0, 2), Point(0, 5)));
+        assert_eq!(board.count_intersections(), 2);
+
+        assert!(board.remove(first).is_some());
+        assert_eq!(board.remove(first), None);
+        assert_eq!(board.count_intersections(), 0);
+        assert_eq!(board.count_at(Point(0, 1)), 0);
+        assert_eq!(board.line(first), None);
+        assert_eq!(board.lines_through(Point(0, 2)), vec![second]);
+    }
+
+    #[test]
+    fn test_both_backends_count_the_same() {
+        let lines = || {
+            vec![
+                Line::new(Point(0, 9), Point(5, 9)),
+                Line::new(Point(8, 0), Point(0, 8)),
+                Line::new(Point(9, 4), Point(3, 4)),
+                Line::new(Point(2, 2), Point(2, 1)),
+                Line::new(Point(7, 0), Point(7, 4)),
+                Line::new(Point(6, 4), Point(2, 0)),
+                Line::new(Point(0, 9), Point(2, 9)),
+                Line::new(Point(3, 4), Point(1, 4)),
+                Line::new(Point(0, 0), Point(8, 8)),
+                Line::new(Point(5, 5), Point(8, 2)),
+            ]
+        };
+        let mut dense = Board::dense(Point(0, 0), Point(9, 9));
+        let mut sparse = Board::sparse();
+        for (first, second) in lines().into_iter().zip(lines()) {
+            dense.draw(first);
+            sparse.draw(second);
+        }
+        assert_eq!(dense.count_intersections(), 12);
+        assert_eq!(sparse.count_intersections(), 12);
     }
 }
@@ -0,0 +1,134 @@
+use crate::domain::{Board, Point};
+use std::io::{self, Write};
+
+// Heat map from cold to hot, interpolated linearly between these colours
+const COLOUR_MAP: [[u8; 3]; 5] = [
+    [0, 0, 0],
+    [0, 0, 255],
+    [255, 0, 0],
+    [255, 255, 0],
+    [255, 255, 255],
+];
+
+impl Board {
+    pub fn bounding_box(&self) -> Option<(Point, Point)> {
+        self.dots()
+            .map(|(point, _)| (point, point))
+            .reduce(|(min, max), (point, _)| {
+                (
+                    Point(min.0.min(point.0), min.1.min(point.1)),
+                    Point(max.0.max(point.0), max.1.max(point.1)),
+                )
+            })
+    }
+
+    pub fn write_pgm<W: Write>(&self, writer: &mut W, scale: usize) -> io::Result<()> {
+        self.write_image(writer, "P5", scale, |intensity| vec![intensity])
+    }
+
+    pub fn write_ppm<W: Write>(&self, writer: &mut W, scale: usize) -> io::Result<()> {
+        self.write_image(writer, "P6", scale, |intensity| colour(intensity).to_vec())
+    }
+
+    fn write_image<W, F>(
+        &self,
+        writer: &mut W,
+        format: &str,
+        scale: usize,
+        pixel: F,
+    ) -> io::Result<()>
+    where
+        W: Write,
+        F: Fn(u8) -> Vec<u8>,
+    {
+        let scale = scale.max(1);
+        let (min, max) = match self.bounding_box() {
+            Some(bounds) => bounds,
+            None => return write!(writer, "{}\n0 0\n255\n", format),
+        };
+        let max_count = self.dots().map(|(_, count)| count).max().unwrap_or(1);
+        let width = (max.0 - min.0 + 1) as usize;
+        write!(
+            writer,
+            "{}\n{} {}\n255\n",
+            format,
+            width * scale,
+            (max.1 - min.1 + 1) as usize * scale
+        )?;
+
+        for y in min.1..=max.1 {
+            let mut row = Vec::with_capacity(width * scale * 3);
+            for x in min.0..=max.0 {
+                let count = self.count_at(Point(x, y)) as u64;
+                let intensity = (count * 255 / max_count as u64) as u8;
+                let pixel = pixel(intensity);
+                for _ in 0..scale {
+                    row.extend_from_slice(&pixel);
+                }
+            }
+            for _ in 0..scale {
+                writer.write_all(&row)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn colour(intensity: u8) -> [u8; 3] {
+    let segments = (COLOUR_MAP.len() - 1) as u32;
+    let position = intensity as u32 * segments;
+    let index = (position / 255).min(segments - 1) as usize;
+    let fraction = position - index as u32 * 255;
+    let (from, to) = (COLOUR_MAP[index], COLOUR_MAP[index + 1]);
+    [0, 1, 2].map(|channel| {
+        let (from, to) = (from[channel] as i32, to[channel] as i32);
+        (from + (to - from) * fraction as i32 / 255) as u8
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::Line;
+
+    fn board() -> Board {
+        let mut board = Board::sparse();
+        board.draw(Line::new(Point(-1, 5), Point(1, 5)));
+        board.draw(Line::new(Point(0, 5), Point(0, 6)));
+        board
+    }
+
+    #[test]
+    fn test_bounding_box() {
+        assert_eq!(board().bounding_box(), Some((Point(-1, 5), Point(1, 6))));
+        assert_eq!(Board::sparse().bounding_box(), None);
+    }
+
+    #[test]
+    fn test_write_pgm() {
+        let mut image = Vec::new();
+        board().write_pgm(&mut image, 1).unwrap();
+        let mut expected = b"P5\n3 2\n255\n".to_vec();
+        expected.extend_from_slice(&[127, 255, 127, 0, 127, 0]);
+        assert_eq!(image, expected);
+    }
+
+    #[test]
+    fn test_write_scaled_ppm() {
+        let mut image = Vec::new();
+        board().write_ppm(&mut image, 2).unwrap();
+        let header = b"P6\n6 4\n255\n";
+        assert_eq!(&image[..header.len()], header);
+        assert_eq!(image.len(), header.len() + 6 * 4 * 3);
+        let pixels = &image[header.len()..];
+        assert_eq!(&pixels[0..3], &pixels[3..6]);
+        assert_eq!(&pixels[6..9], &[255, 255, 255]);
+        assert_eq!(&pixels[18..36], &pixels[0..18]);
+    }
+
+    #[test]
+    fn test_colour_map() {
+        assert_eq!(colour(0), [0, 0, 0]);
+        assert_eq!(colour(255), [255, 255, 255]);
+    }
+}
@@ -0,0 +1,179 @@
+use crate::domain::{gcd, Line, Point};
+use std::collections::{HashMap, HashSet};
+
+type Vector = (i64, i64);
+
+// The infinite line a segment lies on, identified by its normalized direction and offset
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+struct Carrier {
+    direction: Vector,
+    offset: i64,
+}
+
+impl Carrier {
+    // Position of a lattice point along the carrier; consecutive lattice points differ by one
+    fn position(&self, (x, y): Vector) -> i64 {
+        let (dx, dy) = self.direction;
+        if dx != 0 {
+            x.div_euclid(dx)
+        } else {
+            y.div_euclid(dy)
+        }
+    }
+}
+
+struct Segment {
+    start: Vector,
+    step: Vector,
+    steps: i64,
+    carrier: Carrier,
+}
+
+impl Segment {
+    fn new(line: &Line) -> Self {
+        let start = to_vector(line.from());
+        let end = to_vector(line.to());
+        let (dx, dy) = (end.0 - start.0, end.1 - start.1);
+        let steps = gcd(dx.abs(), dy.abs());
+        let step = match steps {
+            0 => (1, 0),
+            _ => (dx / steps, dy / steps),
+        };
+        let direction = match step {
+            (dx, dy) if dx < 0 || (dx == 0 && dy < 0) => (-dx, -dy),
+            step => step,
+        };
+        Segment {
+            start,
+            step,
+            steps,
+            carrier: Carrier {
+                direction,
+                offset: direction.1 * start.0 - direction.0 * start.1,
+            },
+        }
+    }
+
+    fn positions(&self) -> (i64, i64) {
+        let end = (
+            self.start.0 + self.steps * self.step.0,
+            self.start.1 + self.steps * self.step.1,
+        );
+        let (a, b) = (
+            self.carrier.position(self.start),
+            self.carrier.position(end),
+        );
+        (a.min(b), a.max(b))
+    }
+
+    fn crossing(&self, other: &Segment) -> Option<Vector> {
+        let denominator = cross(self.step, other.step);
+        if denominator == 0 {
+            return None;
+        }
+        let between = (other.start.0 - self.start.0, other.start.1 - self.start.1);
+        let s = cross(between, other.step);
+        let t = cross(between, self.step);
+        if s % denominator != 0 || t % denominator != 0 {
+            return None;
+        }
+        let (s, t) = ((s / denominator) as i64, (t / denominator) as i64);
+        if !(0..=self.steps).contains(&s) || !(0..=other.steps).contains(&t) {
+            return None;
+        }
+        Some((
+            self.start.0 + s * self.step.0,
+            self.start.1 + s * self.step.1,
+        ))
+    }
+}
+
+pub fn count_overlaps(lines: &[Line]) -> usize {
+    let segments: Vec<Segment> = lines.iter().map(Segment::new).collect();
+
+    let mut ranges_per_carrier: HashMap<Carrier, Vec<(i64, i64)>> = HashMap::new();
+    for segment in &segments {
+        ranges_per_carrier
+            .entry(segment.carrier)
+            .or_default()
+            .push(segment.positions());
+    }
+    let overlaps_per_carrier: HashMap<Carrier, Vec<(i64, i64)>> = ranges_per_carrier
+        .into_iter()
+        .map(|(carrier, ranges)| (carrier, covered_at_least_twice(ranges)))
+        .filter(|(_, overlaps)| !overlaps.is_empty())
+        .collect();
+
+    let mut crossings: HashMap<Vector, HashSet<Carrier>> = HashMap::new();
+    for (index, first) in segments.iter().enumerate() {
+        for second in &segments[index + 1..] {
+            if let Some(point) = first.crossing(second) {
+                let carriers = crossings.entry(point).or_default();
+                carriers.insert(first.carrier);
+                carriers.insert(second.carrier);
+            }
+        }
+    }
+
+    let mut total: i64 = overlaps_per_carrier
+        .values()
+        .flatten()
+        .map(|(from, to)| to - from + 1)
+        .sum();
+    for (point, carriers) in crossings {
+        let counted = carriers
+            .iter()
+            .filter(|carrier| {
+                overlaps_per_carrier
+                    .get(carrier)
+                    .is_some_and(|overlaps| contains(overlaps, carrier.position(point)))
+            })
+            .count() as i64;
+        // A crossing already counted as a collinear overlap on several carriers was counted too often
+        match counted {
+            0 => total += 1,
+            _ => total -= counted - 1,
+        }
+    }
+    total as usize
+}
+
+fn covered_at_least_twice(ranges: Vec<(i64, i64)>) -> Vec<(i64, i64)> {
+    let mut events: Vec<(i64, i64)> = ranges
+        .into_iter()
+        .flat_map(|(from, to)| [(from, 1), (to + 1, -1)])
+        .collect();
+    events.sort_unstable();
+
+    let mut overlaps: Vec<(i64, i64)> = Vec::new();
+    let mut coverage = 0;
+    for (index, &(position, delta)) in events.iter().enumerate() {
+        coverage += delta;
+        let next = match events.get(index + 1) {
+            Some(&(next, _)) if next > position => next,
+            _ => continue,
+        };
+        if coverage >= 2 {
+            match overlaps.last_mut() {
+                Some((_, to)) if *to + 1 == position => *to = next - 1,
+                _ => overlaps.push((position, next - 1)),
+            }
+        }
+    }
+    overlaps
+}
+
+fn contains(overlaps: &[(i64, i64)], position: i64) -> bool {
+    let index = overlaps.partition_point(|&(_, to)| to < position);
+    overlaps
+        .get(index)
+        .is_some_and(|&(from, _)| from <= position)
+}
+
+fn to_vector(Point(x, y): Point) -> Vector {
+    (x, y)
+}
+
+fn cross(a: Vector, b: Vector) -> i128 {
+    a.0 as i128 * b.1 as i128 - a.1 as i128 * b.0 as i128
+}
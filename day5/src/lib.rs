@@ -1,20 +1,87 @@
 mod domain;
 
-use domain::Board;
-use domain::Line;
+use std::fmt;
+
+pub use domain::{
+    count_overlaps, Board, Coordinate, Line, LineFilter, LineId, Orientation, Point, Rasterization,
+};
+
+#[derive(Debug, Eq, PartialEq)]
+pub struct InvalidLine {
+    pub line_number: usize,
+    pub content: String,
+}
+
+impl fmt::Display for InvalidLine {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}: expected x1,y1 -> x2,y2, found {:?}",
+            self.line_number, self.content
+        )
+    }
+}
 
 pub fn get_number_of_overlapping_lines(contents: &str) -> usize {
-    let mut board = Board::new();
+    count_overlapping_points(contents, LineFilter::All)
+}
+
+pub fn count_overlapping_points(contents: &str, filter: LineFilter) -> usize {
+    count_overlapping_points_with(contents, filter, Rasterization::Lattice)
+}
+
+pub fn count_overlapping_points_with(
+    contents: &str,
+    filter: LineFilter,
+    rasterization: Rasterization,
+) -> usize {
     let lines: Vec<Line> = contents
         .lines()
         .filter_map(|line| line.parse().ok())
         .collect();
+    count_overlapping_lines(&lines, filter, rasterization)
+}
+
+pub fn count_overlapping_lines(
+    lines: &[Line],
+    filter: LineFilter,
+    rasterization: Rasterization,
+) -> usize {
+    let lines: Vec<Line> = lines
+        .iter()
+        .copied()
+        .filter(|line| filter.accepts(line))
+        .collect();
+    let mut board = Board::for_lines(&lines, rasterization);
     for line in lines {
-        board.draw(line);
+        board.draw_with(line, rasterization);
     }
     board.count_intersections()
 }
 
+pub fn count_overlapping_points_analytically(contents: &str, filter: LineFilter) -> usize {
+    let lines: Vec<Line> = contents
+        .lines()
+        .filter_map(|line| line.parse().ok())
+        .filter(|line| filter.accepts(line))
+        .collect();
+    count_overlaps(&lines)
+}
+
+pub fn parse_lines(contents: &str) -> Result<Vec<Line>, InvalidLine> {
+    contents
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| {
+            line.trim().parse().map_err(|_| InvalidLine {
+                line_number: index + 1,
+                content: line.to_string(),
+            })
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -34,4 +101,89 @@ mod tests {
 
         assert_eq!(get_number_of_overlapping_lines(contents), 12);
     }
+
+    #[test]
+    fn can_count_overlapping_points_per_filter() {
+        let contents = "0,9 -> 5,9\n\
+        8,0 -> 0,8\n\
+        9,4 -> 3,4\n\
+        2,2 -> 2,1\n\
+        7,0 -> 7,4\n\
+        6,4 -> 2,0\n\
+        0,9 -> 2,9\n\
+        3,4 -> 1,4\n\
+        0,0 -> 8,8\n\
+        5,5 -> 8,2\n";
+
+        assert_eq!(
+            count_overlapping_points(contents, LineFilter::AxisAligned),
+            5
+        );
+        assert_eq!(count_overlapping_points(contents, LineFilter::Diagonal), 4);
+        assert_eq!(count_overlapping_points(contents, LineFilter::All), 12);
+    }
+
+    #[test]
+    fn can_count_overlapping_points_of_sloped_lines() {
+        let contents = "0,0 -> 6,3\n\
+        2,0 -> 2,4\n\
+        1,0 -> 1,4\n\
+        0,3 -> 6,0\n";
+
+        assert_eq!(count_overlapping_points(contents, LineFilter::All), 2);
+        assert_eq!(
+            count_overlapping_points_analytically(contents, LineFilter::All),
+            2
+        );
+        assert_eq!(
+            count_overlapping_points_with(contents, LineFilter::All, Rasterization::Bresenham),
+            4
+        );
+    }
+
+    #[test]
+    fn can_count_overlapping_points_of_long_oblique_lines() {
+        let contents = "0,0 -> 5000000000,1\n0,0 -> 5000000000,1\n";
+
+        assert_eq!(get_number_of_overlapping_lines(contents), 2);
+    }
+
+    #[test]
+    fn can_count_overlapping_points_analytically() {
+        let input = include_str!("../input.txt");
+        for filter in [
+            LineFilter::AxisAligned,
+            LineFilter::Diagonal,
+            LineFilter::All,
+        ] {
+            assert_eq!(
+                count_overlapping_points_analytically(input, filter),
+                count_overlapping_points(input, filter)
+            );
+        }
+    }
+
+    #[test]
+    fn can_parse_lines() {
+        let lines = parse_lines("0,9 -> 5,9\n\n-8,0 -> 0,8\n").unwrap();
+        assert_eq!(
+            lines,
+            vec![
+                Line::new(Point(0, 9), Point(5, 9)),
+                Line::new(Point(-8, 0), Point(0, 8))
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_invalid_line() {
+        let result = parse_lines("0,9 -> 5,9\n8,0 => 0,8\n");
+        assert_eq!(
+            result,
+            Err(InvalidLine {
+                line_number: 2,
+                content: String::from("8,0 => 0,8")
+            })
+        );
+    }
 }
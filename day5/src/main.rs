@@ -1,6 +1,73 @@
-use day5::get_number_of_overlapping_lines;
+use day5::{count_overlapping_lines, parse_lines, InvalidLine, LineFilter, Rasterization};
+use std::io::Read;
+use std::process::exit;
+use std::{env, fs, io};
 
 fn main() {
-    let contents = include_str!("../input.txt");
-    println!("{}", get_number_of_overlapping_lines(contents));
+    let result = parse_arguments(env::args()).and_then(|(contents, filter)| {
+        let lines = parse_lines(&contents).map_err(Error::CannotParse)?;
+        Ok((lines, filter))
+    });
+    let (lines, filter) = match result {
+        Ok(arguments) => arguments,
+        Err(error) => {
+            match error {
+                Error::NoFilename => {
+                    eprintln!("Error: expected filename or - for stdin as first argument")
+                }
+                Error::CannotRead(error) => eprintln!("Error: cannot read input because {}", error),
+                Error::UnknownFilter(filter) => eprintln!(
+                    "Error: unknown filter {}, expected one of axis, diagonal or all",
+                    filter
+                ),
+                Error::CannotParse(error) => eprintln!("Error: {}", error),
+            }
+            exit(1);
+        }
+    };
+
+    let filters = match filter {
+        Some(filter) => vec![filter],
+        None => vec![
+            LineFilter::AxisAligned,
+            LineFilter::Diagonal,
+            LineFilter::All,
+        ],
+    };
+    for filter in filters {
+        let count = count_overlapping_lines(&lines, filter, Rasterization::Lattice);
+        println!("{:?}: {}", filter, count);
+    }
+}
+
+#[derive(Debug)]
+enum Error {
+    NoFilename,
+    CannotRead(io::Error),
+    UnknownFilter(String),
+    CannotParse(InvalidLine),
+}
+
+fn parse_arguments(args: env::Args) -> Result<(String, Option<LineFilter>), Error> {
+    let (flags, arguments): (Vec<String>, Vec<String>) =
+        args.skip(1).partition(|arg| arg.starts_with("--"));
+
+    let filter = match flags.iter().find_map(|flag| flag.strip_prefix("--filter=")) {
+        Some(filter) => Some(
+            filter
+                .parse()
+                .map_err(|_| Error::UnknownFilter(filter.to_string()))?,
+        ),
+        None => None,
+    };
+
+    let filename = arguments.first().ok_or(Error::NoFilename)?;
+    let contents = match filename.as_str() {
+        "-" => {
+            let mut contents = String::new();
+            io::stdin().read_to_string(&mut contents).map(|_| contents)
+        }
+        filename => fs::read_to_string(filename),
+    };
+    Ok((contents.map_err(Error::CannotRead)?, filter))
 }
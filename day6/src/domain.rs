@@ -1,42 +1,362 @@
+use std::fmt;
 use std::str::FromStr;
 
+mod arithmetic;
+mod big_number;
+mod individual;
+mod limits;
+mod matrix;
+mod report;
+mod threshold;
+
+pub use arithmetic::{Arithmetic, Checked, Exact, Modular, Native};
+pub use big_number::BigNumber;
+pub use individual::{Fish, FishId, IndividualSchool};
+pub use limits::{Limits, Mortality};
+pub use matrix::Matrix;
+pub use report::{DayRecord, PopulationReport};
+
 pub type AmountOfFish = u64;
 pub type FishLifetime = usize;
 
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct SpawnRules {
+    pub cycle_length: usize,
+    pub first_cycle_delay: usize,
+    pub offspring_per_spawn: AmountOfFish,
+}
+
+impl SpawnRules {
+    pub fn number_of_timers(&self) -> usize {
+        self.cycle_length + self.first_cycle_delay
+    }
+
+    // Largest root of x^(cycle + delay) = x^delay + offspring, the long term daily growth factor
+    pub fn asymptotic_growth_rate(&self) -> f64 {
+        let equation = |x: f64| {
+            x.powi(self.number_of_timers() as i32)
+                - x.powi(self.first_cycle_delay as i32)
+                - self.offspring_per_spawn as f64
+        };
+        let (mut low, mut high) = (1.0, 2.0 + self.offspring_per_spawn as f64);
+        for _ in 0..100 {
+            let middle = (low + high) / 2.0;
+            if equation(middle) < 0.0 {
+                low = middle;
+            } else {
+                high = middle;
+            }
+        }
+        low
+    }
+}
+
+impl Default for SpawnRules {
+    fn default() -> Self {
+        SpawnRules {
+            cycle_length: 7,
+            first_cycle_delay: 2,
+            offspring_per_spawn: 1,
+        }
+    }
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum SchoolError {
+    InvalidRules,
+    InvalidLimits,
+    LimitsNotSupported,
+    InvalidModulus,
+    InvalidTimer(String),
+    TimerOutOfRange {
+        timer: FishLifetime,
+        max: FishLifetime,
+    },
+    Overflow {
+        day: u64,
+    },
+}
+
+impl fmt::Display for SchoolError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SchoolError::InvalidRules => write!(f, "cycle length should be at least 1"),
+            SchoolError::InvalidLimits => write!(
+                f,
+                "maximum age should be at least 1 and death fraction between 0 and 1"
+            ),
+            SchoolError::LimitsNotSupported => {
+                write!(f, "limits are only supported when simulating day by day")
+            }
+            SchoolError::InvalidModulus => write!(f, "modulus should be at least 1"),
+            SchoolError::InvalidTimer(timer) => write!(f, "expected a timer, found {:?}", timer),
+            SchoolError::TimerOutOfRange { timer, max } => {
+                write!(f, "expected a timer up to {}, found {}", max, timer)
+            }
+            SchoolError::Overflow { day } => {
+                write!(f, "amount of fish does not fit in 64 bits on day {}", day)
+            }
+        }
+    }
+}
+
+fn validate(fishes: &[FishLifetime], rules: &SpawnRules) -> Result<(), SchoolError> {
+    if rules.cycle_length == 0 {
+        return Err(SchoolError::InvalidRules);
+    }
+    match fishes
+        .iter()
+        .find(|&&fish| fish >= rules.number_of_timers())
+    {
+        Some(&timer) => Err(SchoolError::TimerOutOfRange {
+            timer,
+            max: rules.number_of_timers() - 1,
+        }),
+        None => Ok(()),
+    }
+}
+
+#[derive(Clone)]
 pub struct SchoolOFish {
-    amount_of_fish_per_timer: [AmountOfFish; 9],
+    rules: SpawnRules,
+    limits: Limits,
+    amount_of_fish_per_timer: Vec<AmountOfFish>,
+    // Indexed by age, then timer; only tracked once limits are set
+    amount_of_fish_per_age: Vec<Vec<AmountOfFish>>,
 }
 
 impl SchoolOFish {
-    pub fn new(fishes: Vec<FishLifetime>) -> Self {
-        let mut amount_of_fish_per_timer = [0; 9];
+    pub fn new(fishes: Vec<FishLifetime>) -> Result<Self, SchoolError> {
+        SchoolOFish::with_rules(fishes, SpawnRules::default())
+    }
+
+    pub fn with_rules(fishes: Vec<FishLifetime>, rules: SpawnRules) -> Result<Self, SchoolError> {
+        validate(&fishes, &rules)?;
+        let mut amount_of_fish_per_timer = vec![0; rules.number_of_timers()];
         for fish in fishes {
-            amount_of_fish_per_timer[fish] += 1
+            amount_of_fish_per_timer[fish] += 1;
         }
-        SchoolOFish {
+        Ok(SchoolOFish {
+            rules,
+            limits: Limits::default(),
             amount_of_fish_per_timer,
-        }
+            amount_of_fish_per_age: Vec::new(),
+        })
+    }
+
+    pub fn parse_with_rules(line: &str, rules: SpawnRules) -> Result<Self, SchoolError> {
+        let fish = line
+            .trim_end()
+            .split(',')
+            .map(|fish| {
+                fish.trim()
+                    .parse::<FishLifetime>()
+                    .map_err(|_| SchoolError::InvalidTimer(fish.trim().to_string()))
+            })
+            .collect::<Result<Vec<FishLifetime>, SchoolError>>()?;
+        SchoolOFish::with_rules(fish, rules)
     }
 
     pub fn simulate_day(&mut self) {
-        let [amount_fish_with_zero_left, ..] = self.amount_of_fish_per_timer;
+        if !self.limits.is_unlimited() {
+            self.simulate_limited_day();
+            return;
+        }
+        let amount_fish_with_zero_left = self.amount_of_fish_per_timer[0];
         self.amount_of_fish_per_timer.rotate_left(1);
-        self.amount_of_fish_per_timer[6] += amount_fish_with_zero_left;
+        *self.amount_of_fish_per_timer.last_mut().unwrap() =
+            amount_fish_with_zero_left * self.rules.offspring_per_spawn;
+        self.amount_of_fish_per_timer[self.rules.cycle_length - 1] += amount_fish_with_zero_left;
+    }
+
+    pub fn checked_simulate_day(&mut self) -> Option<()> {
+        if !self.limits.is_unlimited() {
+            return self.checked_simulate_limited_day();
+        }
+        let amount_fish_with_zero_left = self.amount_of_fish_per_timer[0];
+        let mut next_day = self.amount_of_fish_per_timer.clone();
+        next_day.rotate_left(1);
+        *next_day.last_mut().unwrap() =
+            amount_fish_with_zero_left.checked_mul(self.rules.offspring_per_spawn)?;
+        next_day[self.rules.cycle_length - 1] =
+            next_day[self.rules.cycle_length - 1].checked_add(amount_fish_with_zero_left)?;
+        self.amount_of_fish_per_timer = next_day;
+        Some(())
+    }
+
+    // Limits make the population depend on more than the timers, so there is no transition matrix
+    pub fn fast_forward(&mut self, days: u64) -> Result<(), SchoolError> {
+        if !self.limits.is_unlimited() {
+            return Err(SchoolError::LimitsNotSupported);
+        }
+        let timers: Vec<Option<AmountOfFish>> = self
+            .amount_of_fish_per_timer
+            .iter()
+            .map(|&amount| Some(amount))
+            .collect();
+        let amount_of_fish_per_timer: Vec<AmountOfFish> = Matrix::transition(&self.rules)
+            .convert(&Checked)
+            .power(days, &Checked)
+            .apply(&timers, &Checked)
+            .into_iter()
+            .collect::<Option<_>>()
+            .ok_or(SchoolError::Overflow { day: days })?;
+        amount_of_fish_per_timer
+            .iter()
+            .try_fold(0, |total: AmountOfFish, &amount| total.checked_add(amount))
+            .ok_or(SchoolError::Overflow { day: days })?;
+        self.amount_of_fish_per_timer = amount_of_fish_per_timer;
+        Ok(())
+    }
+
+    pub fn project<A: Arithmetic>(
+        &self,
+        days: u64,
+        arithmetic: &A,
+    ) -> Result<A::Value, SchoolError> {
+        if !self.limits.is_unlimited() {
+            return Err(SchoolError::LimitsNotSupported);
+        }
+        let timers: Vec<A::Value> = self
+            .amount_of_fish_per_timer
+            .iter()
+            .map(|&amount| arithmetic.amount(amount))
+            .collect();
+        let total = Matrix::transition(&self.rules)
+            .convert(arithmetic)
+            .power(days, arithmetic)
+            .apply(&timers, arithmetic)
+            .iter()
+            .fold(arithmetic.amount(0), |total, amount| {
+                arithmetic.add(&total, amount)
+            });
+        Ok(total)
+    }
+
+    pub fn rules(&self) -> &SpawnRules {
+        &self.rules
+    }
+
+    pub fn amount_of_fish_per_timer(&self) -> &[AmountOfFish] {
+        &self.amount_of_fish_per_timer
     }
 
     pub fn total(&self) -> AmountOfFish {
         self.amount_of_fish_per_timer.iter().sum()
     }
+
+    pub fn checked_total(&self) -> Option<AmountOfFish> {
+        self.amount_of_fish_per_timer
+            .iter()
+            .try_fold(0, |total: AmountOfFish, &amount| total.checked_add(amount))
+    }
 }
 
 impl FromStr for SchoolOFish {
-    type Err = ();
+    type Err = SchoolError;
 
     fn from_str(line: &str) -> Result<Self, Self::Err> {
-        let fish: Vec<usize> = line
-            .split(',')
-            .filter_map(|fish| fish.trim_end().parse::<FishLifetime>().ok())
+        SchoolOFish::parse_with_rules(line, SpawnRules::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn timer_out_of_range_is_an_error() {
+        let result = SchoolOFish::new(vec![3, 9]);
+        assert_eq!(
+            result.err(),
+            Some(SchoolError::TimerOutOfRange { timer: 9, max: 8 })
+        );
+    }
+
+    #[test]
+    fn invalid_timer_is_an_error() {
+        let result = "3,x,1".parse::<SchoolOFish>();
+        assert_eq!(
+            result.err(),
+            Some(SchoolError::InvalidTimer(String::from("x")))
+        );
+    }
+
+    #[test]
+    fn custom_rules() {
+        let rules = SpawnRules {
+            cycle_length: 3,
+            first_cycle_delay: 1,
+            offspring_per_spawn: 2,
+        };
+        let mut school = SchoolOFish::with_rules(vec![0], rules).unwrap();
+        let totals: Vec<AmountOfFish> = (0..5)
+            .map(|_| {
+                school.simulate_day();
+                school.total()
+            })
             .collect();
-        Ok(SchoolOFish::new(fish))
+        assert_eq!(totals, vec![3, 3, 3, 5, 9]);
+    }
+
+    #[test]
+    fn fast_forward_matches_simulation() {
+        let rules = SpawnRules {
+            cycle_length: 5,
+            first_cycle_delay: 3,
+            offspring_per_spawn: 3,
+        };
+        let mut simulated = SchoolOFish::with_rules(vec![1, 4, 7, 0], rules).unwrap();
+        let mut fast_forwarded = SchoolOFish::with_rules(vec![1, 4, 7, 0], rules).unwrap();
+        (0..40).for_each(|_| simulated.simulate_day());
+        fast_forwarded.fast_forward(40).unwrap();
+        assert_eq!(
+            simulated.amount_of_fish_per_timer,
+            fast_forwarded.amount_of_fish_per_timer
+        );
+    }
+
+    #[test]
+    fn checked_simulation_detects_overflow() {
+        let mut school = SchoolOFish::new(vec![0; 1]).unwrap();
+        school.amount_of_fish_per_timer[0] = AmountOfFish::MAX / 2 + 1;
+        school.amount_of_fish_per_timer[7] = AmountOfFish::MAX / 2 + 1;
+        assert_eq!(school.checked_simulate_day(), None);
+        assert_eq!(
+            school.amount_of_fish_per_timer[0],
+            AmountOfFish::MAX / 2 + 1
+        );
+    }
+
+    #[test]
+    fn project_in_every_arithmetic() {
+        let school = SchoolOFish::new(vec![3, 4, 3, 1, 2]).unwrap();
+        assert_eq!(school.project(80, &Native), Ok(5934));
+        assert_eq!(school.project(80, &Exact), Ok(BigNumber::from(5934)));
+        assert_eq!(school.project(80, &Modular::new(1000).unwrap()), Ok(934));
+        assert!(matches!(Modular::new(0), Err(SchoolError::InvalidModulus)));
+    }
+
+    #[test]
+    fn asymptotic_growth_rate() {
+        let rate = SpawnRules::default().asymptotic_growth_rate();
+        assert!((rate.powi(9) - rate.powi(2) - 1.0).abs() < 1e-9);
+        let mut school = SchoolOFish::new(vec![3, 4, 3, 1, 2]).unwrap();
+        (0..400).for_each(|_| school.simulate_day());
+        let previous = school.total() as f64;
+        school.simulate_day();
+        assert!((school.total() as f64 / previous - rate).abs() < 1e-3);
+    }
+
+    #[test]
+    fn newborns_without_first_cycle_delay() {
+        let rules = SpawnRules {
+            cycle_length: 2,
+            first_cycle_delay: 0,
+            offspring_per_spawn: 1,
+        };
+        let mut school = SchoolOFish::with_rules(vec![0], rules).unwrap();
+        school.simulate_day();
+        assert_eq!(school.amount_of_fish_per_timer, vec![0, 2]);
     }
 }
@@ -0,0 +1,93 @@
+use crate::domain::big_number::BigNumber;
+use crate::domain::{AmountOfFish, SchoolError};
+
+// Number type used to count fish, so the same calculation works for every counting mode
+pub trait Arithmetic {
+    type Value: Clone;
+
+    fn amount(&self, amount: AmountOfFish) -> Self::Value;
+    fn add(&self, a: &Self::Value, b: &Self::Value) -> Self::Value;
+    fn multiply(&self, a: &Self::Value, b: &Self::Value) -> Self::Value;
+}
+
+pub struct Native;
+
+impl Arithmetic for Native {
+    type Value = AmountOfFish;
+
+    fn amount(&self, amount: AmountOfFish) -> AmountOfFish {
+        amount
+    }
+
+    fn add(&self, a: &AmountOfFish, b: &AmountOfFish) -> AmountOfFish {
+        a + b
+    }
+
+    fn multiply(&self, a: &AmountOfFish, b: &AmountOfFish) -> AmountOfFish {
+        a * b
+    }
+}
+
+// Like Native, but a value that does not fit stays None instead of wrapping or panicking
+pub struct Checked;
+
+impl Arithmetic for Checked {
+    type Value = Option<AmountOfFish>;
+
+    fn amount(&self, amount: AmountOfFish) -> Option<AmountOfFish> {
+        Some(amount)
+    }
+
+    fn add(&self, a: &Option<AmountOfFish>, b: &Option<AmountOfFish>) -> Option<AmountOfFish> {
+        a.and_then(|a| a.checked_add((*b)?))
+    }
+
+    fn multiply(&self, a: &Option<AmountOfFish>, b: &Option<AmountOfFish>) -> Option<AmountOfFish> {
+        a.and_then(|a| a.checked_mul((*b)?))
+    }
+}
+
+pub struct Exact;
+
+impl Arithmetic for Exact {
+    type Value = BigNumber;
+
+    fn amount(&self, amount: AmountOfFish) -> BigNumber {
+        BigNumber::from(amount)
+    }
+
+    fn add(&self, a: &BigNumber, b: &BigNumber) -> BigNumber {
+        a + b
+    }
+
+    fn multiply(&self, a: &BigNumber, b: &BigNumber) -> BigNumber {
+        a * b
+    }
+}
+
+pub struct Modular(u64);
+
+impl Modular {
+    pub fn new(modulus: u64) -> Result<Self, SchoolError> {
+        match modulus {
+            0 => Err(SchoolError::InvalidModulus),
+            _ => Ok(Modular(modulus)),
+        }
+    }
+}
+
+impl Arithmetic for Modular {
+    type Value = u64;
+
+    fn amount(&self, amount: AmountOfFish) -> u64 {
+        amount % self.0
+    }
+
+    fn add(&self, a: &u64, b: &u64) -> u64 {
+        ((*a as u128 + *b as u128) % self.0 as u128) as u64
+    }
+
+    fn multiply(&self, a: &u64, b: &u64) -> u64 {
+        ((*a as u128 * *b as u128) % self.0 as u128) as u64
+    }
+}
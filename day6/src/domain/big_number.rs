@@ -0,0 +1,149 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, Mul};
+
+// Unsigned integer of any size, stored as base 2^32 limbs with the least significant first
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct BigNumber {
+    limbs: Vec<u32>,
+}
+
+impl BigNumber {
+    pub fn is_zero(&self) -> bool {
+        self.limbs.is_empty()
+    }
+
+    pub fn divide_small(&self, divisor: u32) -> Option<(BigNumber, u32)> {
+        if divisor == 0 {
+            return None;
+        }
+        let mut quotient = vec![0; self.limbs.len()];
+        let mut remainder: u64 = 0;
+        for (index, &limb) in self.limbs.iter().enumerate().rev() {
+            let current = (remainder << 32) | limb as u64;
+            quotient[index] = (current / divisor as u64) as u32;
+            remainder = current % divisor as u64;
+        }
+        Some((BigNumber::from_limbs(quotient), remainder as u32))
+    }
+
+    fn from_limbs(mut limbs: Vec<u32>) -> Self {
+        while limbs.last() == Some(&0) {
+            limbs.pop();
+        }
+        BigNumber { limbs }
+    }
+}
+
+impl From<u64> for BigNumber {
+    fn from(value: u64) -> Self {
+        BigNumber::from_limbs(vec![value as u32, (value >> 32) as u32])
+    }
+}
+
+impl Add for &BigNumber {
+    type Output = BigNumber;
+
+    fn add(self, other: &BigNumber) -> BigNumber {
+        let length = self.limbs.len().max(other.limbs.len());
+        let mut limbs = Vec::with_capacity(length + 1);
+        let mut carry: u64 = 0;
+        for index in 0..length {
+            let sum = carry
+                + *self.limbs.get(index).unwrap_or(&0) as u64
+                + *other.limbs.get(index).unwrap_or(&0) as u64;
+            limbs.push(sum as u32);
+            carry = sum >> 32;
+        }
+        limbs.push(carry as u32);
+        BigNumber::from_limbs(limbs)
+    }
+}
+
+impl Mul for &BigNumber {
+    type Output = BigNumber;
+
+    fn mul(self, other: &BigNumber) -> BigNumber {
+        let mut limbs = vec![0u32; self.limbs.len() + other.limbs.len()];
+        for (i, &a) in self.limbs.iter().enumerate() {
+            let mut carry: u64 = 0;
+            for (j, &b) in other.limbs.iter().enumerate() {
+                let product = a as u64 * b as u64 + limbs[i + j] as u64 + carry;
+                limbs[i + j] = product as u32;
+                carry = product >> 32;
+            }
+            limbs[i + other.limbs.len()] = carry as u32;
+        }
+        BigNumber::from_limbs(limbs)
+    }
+}
+
+impl Ord for BigNumber {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.limbs
+            .len()
+            .cmp(&other.limbs.len())
+            .then_with(|| self.limbs.iter().rev().cmp(other.limbs.iter().rev()))
+    }
+}
+
+impl PartialOrd for BigNumber {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for BigNumber {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_zero() {
+            return write!(f, "0");
+        }
+        let mut chunks = Vec::new();
+        let mut rest = self.clone();
+        while !rest.is_zero() {
+            let (quotient, remainder) = rest.divide_small(1_000_000_000).unwrap();
+            chunks.push(remainder);
+            rest = quotient;
+        }
+        write!(f, "{}", chunks.pop().unwrap())?;
+        for chunk in chunks.iter().rev() {
+            write!(f, "{:09}", chunk)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn add_and_multiply_past_u64() {
+        let max = BigNumber::from(u64::MAX);
+        let sum = &max + &BigNumber::from(1);
+        assert_eq!(sum.to_string(), "18446744073709551616");
+        let product = &max * &max;
+        assert_eq!(
+            product.to_string(),
+            "340282366920938463426481119284349108225"
+        );
+    }
+
+    #[test]
+    fn compare() {
+        let small = BigNumber::from(5);
+        let large = &BigNumber::from(u64::MAX) + &small;
+        assert!(small < large);
+        assert_eq!(BigNumber::from(0).to_string(), "0");
+    }
+
+    #[test]
+    fn divide_small() {
+        let number = BigNumber::from(1_000_000_007);
+        assert_eq!(
+            number.divide_small(10),
+            Some((BigNumber::from(100_000_000), 7))
+        );
+        assert_eq!(number.divide_small(0), None);
+    }
+}
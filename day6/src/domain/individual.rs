@@ -0,0 +1,190 @@
+use crate::domain::{validate, FishLifetime, SchoolError, SpawnRules};
+
+pub type FishId = usize;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Fish {
+    pub id: FishId,
+    pub parent: Option<FishId>,
+    pub birth_day: u64,
+    pub timer: FishLifetime,
+}
+
+// Tracks every fish on its own, only feasible for small schools
+pub struct IndividualSchool {
+    rules: SpawnRules,
+    fish: Vec<Fish>,
+    day: u64,
+}
+
+impl IndividualSchool {
+    pub fn new(timers: Vec<FishLifetime>) -> Result<Self, SchoolError> {
+        IndividualSchool::with_rules(timers, SpawnRules::default())
+    }
+
+    pub fn with_rules(timers: Vec<FishLifetime>, rules: SpawnRules) -> Result<Self, SchoolError> {
+        validate(&timers, &rules)?;
+        let fish = timers
+            .into_iter()
+            .enumerate()
+            .map(|(id, timer)| Fish {
+                id,
+                parent: None,
+                birth_day: 0,
+                timer,
+            })
+            .collect();
+        Ok(IndividualSchool {
+            rules,
+            fish,
+            day: 0,
+        })
+    }
+
+    pub fn simulate_day(&mut self) {
+        self.day += 1;
+        let mut newborns = Vec::new();
+        for fish in &mut self.fish {
+            if fish.timer == 0 {
+                fish.timer = self.rules.cycle_length - 1;
+                for _ in 0..self.rules.offspring_per_spawn {
+                    newborns.push((fish.id, self.day));
+                }
+            } else {
+                fish.timer -= 1;
+            }
+        }
+        for (parent, birth_day) in newborns {
+            self.fish.push(Fish {
+                id: self.fish.len(),
+                parent: Some(parent),
+                birth_day,
+                timer: self.rules.number_of_timers() - 1,
+            });
+        }
+    }
+
+    pub fn day(&self) -> u64 {
+        self.day
+    }
+
+    pub fn fish(&self) -> &[Fish] {
+        &self.fish
+    }
+
+    pub fn total(&self) -> usize {
+        self.fish.len()
+    }
+
+    pub fn amount_of_fish_per_timer(&self) -> Vec<u64> {
+        let mut amount_of_fish_per_timer = vec![0; self.rules.number_of_timers()];
+        for fish in &self.fish {
+            amount_of_fish_per_timer[fish.timer] += 1;
+        }
+        amount_of_fish_per_timer
+    }
+
+    // Children always have a larger id than their parent, so one pass per direction suffices
+    pub fn generations(&self) -> Vec<usize> {
+        let mut generations: Vec<usize> = Vec::with_capacity(self.fish.len());
+        for fish in &self.fish {
+            let generation = fish.parent.map_or(0, |parent| generations[parent] + 1);
+            generations.push(generation);
+        }
+        generations
+    }
+
+    pub fn descendants(&self) -> Vec<usize> {
+        let mut descendants = vec![0; self.fish.len()];
+        for fish in self.fish.iter().rev() {
+            if let Some(parent) = fish.parent {
+                descendants[parent] += descendants[fish.id] + 1;
+            }
+        }
+        descendants
+    }
+
+    pub fn descendants_per_original(&self) -> Vec<(FishId, usize)> {
+        let descendants = self.descendants();
+        self.fish
+            .iter()
+            .filter(|fish| fish.parent.is_none())
+            .map(|fish| (fish.id, descendants[fish.id]))
+            .collect()
+    }
+
+    pub fn generation_depth(&self) -> usize {
+        self.generations().into_iter().max().unwrap_or(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::SchoolOFish;
+
+    #[test]
+    fn matches_bucket_model() {
+        let rules = SpawnRules {
+            cycle_length: 5,
+            first_cycle_delay: 1,
+            offspring_per_spawn: 2,
+        };
+        let mut individual = IndividualSchool::with_rules(vec![3, 4, 3, 1, 2], rules).unwrap();
+        let mut buckets = SchoolOFish::with_rules(vec![3, 4, 3, 1, 2], rules).unwrap();
+        for _ in 0..30 {
+            individual.simulate_day();
+            buckets.simulate_day();
+            assert_eq!(individual.total() as u64, buckets.total());
+            assert_eq!(
+                individual.amount_of_fish_per_timer(),
+                buckets.amount_of_fish_per_timer()
+            );
+        }
+    }
+
+    #[test]
+    fn matches_example_after_80_days() {
+        let mut school = IndividualSchool::new(vec![3, 4, 3, 1, 2]).unwrap();
+        (0..80).for_each(|_| school.simulate_day());
+        assert_eq!(school.total(), 5934);
+        let descendants: usize = school
+            .descendants_per_original()
+            .iter()
+            .map(|&(_, descendants)| descendants)
+            .sum();
+        assert_eq!(descendants, 5934 - 5);
+    }
+
+    #[test]
+    fn lineage() {
+        let mut school = IndividualSchool::new(vec![0]).unwrap();
+        (0..9).for_each(|_| school.simulate_day());
+        assert_eq!(school.total(), 3);
+        assert_eq!(
+            school.fish()[1],
+            Fish {
+                id: 1,
+                parent: Some(0),
+                birth_day: 1,
+                timer: 0,
+            }
+        );
+        assert_eq!(school.fish()[2].birth_day, 8);
+        assert_eq!(school.fish()[2].parent, Some(0));
+        assert_eq!(school.descendants_per_original(), vec![(0, 2)]);
+        assert_eq!(school.generation_depth(), 1);
+
+        (0..9).for_each(|_| school.simulate_day());
+        assert_eq!(school.generation_depth(), 2);
+        assert_eq!(school.generations()[0], 0);
+    }
+
+    #[test]
+    fn invalid_timer() {
+        assert_eq!(
+            IndividualSchool::new(vec![9]).err(),
+            Some(SchoolError::TimerOutOfRange { timer: 9, max: 8 })
+        );
+    }
+}
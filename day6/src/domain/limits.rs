@@ -0,0 +1,344 @@
+use crate::domain::{AmountOfFish, BigNumber, SchoolError, SchoolOFish};
+
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub enum Mortality {
+    #[default]
+    None,
+    MaximumAge(usize),
+    DailyFraction(f64),
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct Limits {
+    pub mortality: Mortality,
+    pub carrying_capacity: Option<AmountOfFish>,
+}
+
+impl Limits {
+    pub fn is_unlimited(&self) -> bool {
+        *self == Limits::default()
+    }
+
+    fn number_of_ages(&self) -> usize {
+        match self.mortality {
+            Mortality::MaximumAge(maximum_age) => maximum_age,
+            _ => 1,
+        }
+    }
+}
+
+impl SchoolOFish {
+    // The fish already in the school start out at age 0
+    pub fn with_limits(mut self, limits: Limits) -> Result<Self, SchoolError> {
+        match limits.mortality {
+            Mortality::MaximumAge(0) => return Err(SchoolError::InvalidLimits),
+            Mortality::DailyFraction(fraction) if !(0.0..=1.0).contains(&fraction) => {
+                return Err(SchoolError::InvalidLimits)
+            }
+            _ => {}
+        }
+        let mut amount_of_fish_per_age =
+            vec![vec![0; self.rules.number_of_timers()]; limits.number_of_ages()];
+        amount_of_fish_per_age[0] = self.amount_of_fish_per_timer.clone();
+        self.limits = limits;
+        self.amount_of_fish_per_age = amount_of_fish_per_age;
+        Ok(self)
+    }
+
+    pub fn limits(&self) -> &Limits {
+        &self.limits
+    }
+
+    pub(crate) fn simulate_limited_day(&mut self) {
+        // Overflow behaves as in the unlimited simulation
+        self.limited_day(|a, b| Some(a + b), |a, b| Some(a * b));
+    }
+
+    pub(crate) fn checked_simulate_limited_day(&mut self) -> Option<()> {
+        self.limited_day(AmountOfFish::checked_add, AmountOfFish::checked_mul)
+    }
+
+    fn limited_day(
+        &mut self,
+        add: fn(AmountOfFish, AmountOfFish) -> Option<AmountOfFish>,
+        multiply: fn(AmountOfFish, AmountOfFish) -> Option<AmountOfFish>,
+    ) -> Option<()> {
+        let number_of_timers = self.rules.number_of_timers();
+        let mut cohorts = self.amount_of_fish_per_age.clone();
+        if let Mortality::DailyFraction(fraction) = self.limits.mortality {
+            // Deaths round up, so any fraction above zero also wipes out the smallest buckets
+            for amount in cohorts.iter_mut().flatten() {
+                let deaths = (*amount as f64 * fraction).ceil() as AmountOfFish;
+                *amount -= deaths.min(*amount);
+            }
+        }
+
+        let survivors = cohorts
+            .iter()
+            .flatten()
+            .try_fold(0, |total, &amount| add(total, amount))?;
+        let parents = cohorts.iter().map(|cohort| cohort[0]).sum::<AmountOfFish>();
+        let mut newborns = multiply(parents, self.rules.offspring_per_spawn)?;
+        if let Some(capacity) = self.limits.carrying_capacity {
+            newborns = newborns.min(capacity.saturating_sub(survivors));
+        }
+
+        for cohort in &mut cohorts {
+            let parents = cohort[0];
+            cohort.rotate_left(1);
+            *cohort.last_mut().unwrap() = 0;
+            cohort[self.rules.cycle_length - 1] += parents;
+        }
+        if let Mortality::MaximumAge(_) = self.limits.mortality {
+            cohorts.pop();
+            cohorts.insert(0, vec![0; number_of_timers]);
+        }
+        cohorts[0][number_of_timers - 1] += newborns;
+
+        self.amount_of_fish_per_timer = (0..number_of_timers)
+            .map(|timer| {
+                cohorts
+                    .iter()
+                    .try_fold(0, |total, cohort| add(total, cohort[timer]))
+            })
+            .collect::<Option<Vec<AmountOfFish>>>()?;
+        self.amount_of_fish_per_age = cohorts;
+        Some(())
+    }
+
+    // A limited population either outgrows 64 bits or ends up repeating a state, so this
+    // terminates. Repeats are found with Brent's cycle detection, keeping a single saved state.
+    pub(crate) fn first_limited_day_reaching(
+        &self,
+        threshold: &BigNumber,
+    ) -> Result<Option<u64>, SchoolError> {
+        let mut school = self.clone();
+        let mut saved = school.amount_of_fish_per_age.clone();
+        let mut day = 0;
+        loop {
+            let total = school
+                .checked_total()
+                .ok_or(SchoolError::Overflow { day })?;
+            if BigNumber::from(total) >= *threshold {
+                return Ok(Some(day));
+            }
+            school
+                .checked_simulate_limited_day()
+                .ok_or(SchoolError::Overflow { day: day + 1 })?;
+            day += 1;
+            if school.amount_of_fish_per_age == saved {
+                return Ok(None);
+            }
+            if day.is_power_of_two() {
+                saved = school.amount_of_fish_per_age.clone();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{Exact, Modular};
+
+    fn simulate(limits: Limits, days: usize) -> Vec<AmountOfFish> {
+        let mut school = SchoolOFish::new(vec![3, 4, 3, 1, 2])
+            .unwrap()
+            .with_limits(limits)
+            .unwrap();
+        (0..days)
+            .map(|_| {
+                school.simulate_day();
+                school.total()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn without_effective_limits_matches_unlimited_model() {
+        let mut school = SchoolOFish::new(vec![3, 4, 3, 1, 2]).unwrap();
+        let expected: Vec<AmountOfFish> = (0..80)
+            .map(|_| {
+                school.simulate_day();
+                school.total()
+            })
+            .collect();
+        let limits =
+            [Mortality::MaximumAge(100), Mortality::DailyFraction(0.0)].map(|mortality| Limits {
+                mortality,
+                carrying_capacity: Some(AmountOfFish::MAX),
+            });
+        for limits in limits {
+            assert_eq!(simulate(limits, 80), expected);
+        }
+    }
+
+    #[test]
+    fn maximum_age() {
+        let limits = Limits {
+            mortality: Mortality::MaximumAge(3),
+            carrying_capacity: None,
+        };
+        let mut school = SchoolOFish::new(vec![0, 5])
+            .unwrap()
+            .with_limits(limits)
+            .unwrap();
+        school.simulate_day();
+        assert_eq!(school.total(), 3);
+        school.simulate_day();
+        school.simulate_day();
+        assert_eq!(school.total(), 1);
+        assert_eq!(school.amount_of_fish_per_timer()[8], 0);
+        assert_eq!(school.amount_of_fish_per_timer()[6], 1);
+        school.simulate_day();
+        assert_eq!(school.total(), 0);
+    }
+
+    #[test]
+    fn daily_fraction() {
+        let limits = Limits {
+            mortality: Mortality::DailyFraction(0.5),
+            carrying_capacity: None,
+        };
+        let mut school = SchoolOFish::new(vec![4; 100])
+            .unwrap()
+            .with_limits(limits)
+            .unwrap();
+        school.simulate_day();
+        assert_eq!(school.amount_of_fish_per_timer()[3], 50);
+        school.simulate_day();
+        assert_eq!(school.amount_of_fish_per_timer()[2], 25);
+    }
+
+    #[test]
+    fn daily_fraction_kills_single_fish() {
+        let limits = Limits {
+            mortality: Mortality::DailyFraction(0.1),
+            carrying_capacity: None,
+        };
+        let mut school = SchoolOFish::new(vec![4])
+            .unwrap()
+            .with_limits(limits)
+            .unwrap();
+        school.simulate_day();
+        assert_eq!(school.total(), 0);
+    }
+
+    #[test]
+    fn daily_fraction_never_kills_more_than_alive() {
+        let limits = Limits {
+            mortality: Mortality::DailyFraction(1.0),
+            carrying_capacity: None,
+        };
+        let mut school = SchoolOFish::new(vec![4])
+            .unwrap()
+            .with_limits(limits)
+            .unwrap();
+        // Rounds up to 2^53 + 4 as a float
+        school.amount_of_fish_per_age[0][4] = (1 << 53) + 3;
+        school.simulate_day();
+        assert_eq!(school.total(), 0);
+    }
+
+    #[test]
+    fn carrying_capacity_limits_spawning() {
+        let limits = Limits {
+            mortality: Mortality::None,
+            carrying_capacity: Some(100),
+        };
+        let totals = simulate(limits, 200);
+        assert!(totals.iter().all(|&total| total <= 100));
+        assert_eq!(totals.last(), Some(&100));
+        assert!(totals.windows(2).all(|days| days[0] <= days[1]));
+    }
+
+    #[test]
+    fn invalid_limits() {
+        for mortality in [
+            Mortality::MaximumAge(0),
+            Mortality::DailyFraction(-0.1),
+            Mortality::DailyFraction(1.5),
+        ] {
+            let limits = Limits {
+                mortality,
+                carrying_capacity: None,
+            };
+            assert_eq!(
+                SchoolOFish::new(vec![3]).unwrap().with_limits(limits).err(),
+                Some(SchoolError::InvalidLimits)
+            );
+        }
+    }
+
+    #[test]
+    fn threshold_with_limits() {
+        let limits = Limits {
+            mortality: Mortality::None,
+            carrying_capacity: Some(100),
+        };
+        let school = SchoolOFish::new(vec![3, 4, 3, 1, 2])
+            .unwrap()
+            .with_limits(limits)
+            .unwrap();
+        let expected = simulate(limits, 200).iter().position(|&total| total >= 50);
+        assert_eq!(
+            school.first_day_reaching(&50.into()),
+            Ok(expected.map(|day| day as u64 + 1))
+        );
+        assert_eq!(school.first_day_reaching(&101.into()), Ok(None));
+    }
+
+    #[test]
+    fn threshold_beyond_64_bits_with_limits() {
+        let limits = Limits {
+            mortality: Mortality::MaximumAge(1000),
+            carrying_capacity: None,
+        };
+        let school = SchoolOFish::new(vec![3, 4, 3, 1, 2])
+            .unwrap()
+            .with_limits(limits)
+            .unwrap();
+        let threshold = &BigNumber::from(u64::MAX) * &BigNumber::from(10);
+        assert!(matches!(
+            school.first_day_reaching(&threshold),
+            Err(SchoolError::Overflow { .. })
+        ));
+    }
+
+    #[test]
+    fn threshold_never_reached_by_a_dying_population() {
+        let limits = Limits {
+            mortality: Mortality::MaximumAge(3),
+            carrying_capacity: None,
+        };
+        let school = SchoolOFish::new(vec![5, 6])
+            .unwrap()
+            .with_limits(limits)
+            .unwrap();
+        assert_eq!(school.first_day_reaching(&3.into()), Ok(None));
+    }
+
+    #[test]
+    fn shortcuts_reject_limits() {
+        let limits = Limits {
+            mortality: Mortality::MaximumAge(1000),
+            carrying_capacity: None,
+        };
+        let mut school = SchoolOFish::new(vec![3, 4, 3, 1, 2])
+            .unwrap()
+            .with_limits(limits)
+            .unwrap();
+        assert_eq!(
+            school.project(1000, &Exact),
+            Err(SchoolError::LimitsNotSupported)
+        );
+        assert_eq!(
+            school.project(1_000_000_000_000, &Modular::new(1_000_000_007).unwrap()),
+            Err(SchoolError::LimitsNotSupported)
+        );
+        assert_eq!(
+            school.fast_forward(80),
+            Err(SchoolError::LimitsNotSupported)
+        );
+    }
+}
@@ -0,0 +1,113 @@
+use crate::domain::arithmetic::{Arithmetic, Native};
+use crate::domain::{AmountOfFish, SpawnRules};
+
+// Square matrix mapping the fish per timer of one day onto the next
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Matrix<T = AmountOfFish> {
+    size: usize,
+    values: Vec<T>,
+}
+
+impl Matrix {
+    pub fn transition(rules: &SpawnRules) -> Self {
+        let size = rules.number_of_timers();
+        let mut values = vec![0; size * size];
+        for timer in 1..size {
+            values[(timer - 1) * size + timer] = 1;
+        }
+        values[(rules.cycle_length - 1) * size] += 1;
+        values[(size - 1) * size] += rules.offspring_per_spawn;
+        Matrix { size, values }
+    }
+
+    pub fn identity(size: usize) -> Self {
+        Matrix::identity_with(size, &Native)
+    }
+
+    pub fn convert<A: Arithmetic>(&self, arithmetic: &A) -> Matrix<A::Value> {
+        Matrix {
+            size: self.size,
+            values: self
+                .values
+                .iter()
+                .map(|&value| arithmetic.amount(value))
+                .collect(),
+        }
+    }
+}
+
+impl<T: Clone> Matrix<T> {
+    pub fn identity_with<A: Arithmetic<Value = T>>(size: usize, arithmetic: &A) -> Self {
+        let mut values = vec![arithmetic.amount(0); size * size];
+        for index in 0..size {
+            values[index * size + index] = arithmetic.amount(1);
+        }
+        Matrix { size, values }
+    }
+
+    pub fn multiply<A: Arithmetic<Value = T>>(&self, other: &Matrix<T>, arithmetic: &A) -> Self {
+        let size = self.size;
+        let mut values = Vec::with_capacity(size * size);
+        for row in 0..size {
+            for column in 0..size {
+                let value = (0..size).fold(arithmetic.amount(0), |sum, k| {
+                    let product = arithmetic.multiply(
+                        &self.values[row * size + k],
+                        &other.values[k * size + column],
+                    );
+                    arithmetic.add(&sum, &product)
+                });
+                values.push(value);
+            }
+        }
+        Matrix { size, values }
+    }
+
+    pub fn power<A: Arithmetic<Value = T>>(&self, mut exponent: u64, arithmetic: &A) -> Self {
+        let mut result = Matrix::identity_with(self.size, arithmetic);
+        let mut base = self.clone();
+        while exponent > 0 {
+            if exponent & 1 == 1 {
+                result = result.multiply(&base, arithmetic);
+            }
+            exponent >>= 1;
+            if exponent > 0 {
+                base = base.multiply(&base, arithmetic);
+            }
+        }
+        result
+    }
+
+    pub fn apply<A: Arithmetic<Value = T>>(&self, vector: &[T], arithmetic: &A) -> Vec<T> {
+        (0..self.size)
+            .map(|row| {
+                (0..self.size).fold(arithmetic.amount(0), |sum, column| {
+                    let product = arithmetic
+                        .multiply(&self.values[row * self.size + column], &vector[column]);
+                    arithmetic.add(&sum, &product)
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn power_of_zero_is_identity() {
+        let transition = Matrix::transition(&SpawnRules::default());
+        assert_eq!(transition.power(0, &Native), Matrix::identity(9));
+    }
+
+    #[test]
+    fn power_is_repeated_multiplication() {
+        let transition = Matrix::transition(&SpawnRules::default());
+        let mut expected = Matrix::identity(9);
+        for _ in 0..13 {
+            expected = expected.multiply(&transition, &Native);
+        }
+        assert_eq!(transition.power(13, &Native), expected);
+    }
+}
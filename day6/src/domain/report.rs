@@ -0,0 +1,154 @@
+use crate::domain::{AmountOfFish, SchoolError, SchoolOFish};
+use std::fmt::Write;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct DayRecord {
+    pub day: u64,
+    pub total: AmountOfFish,
+    pub amount_of_fish_per_timer: Vec<AmountOfFish>,
+    pub growth_rate: Option<f64>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct PopulationReport {
+    pub asymptotic_growth_rate: f64,
+    pub days: Vec<DayRecord>,
+}
+
+impl PopulationReport {
+    pub fn simulate(mut school: SchoolOFish, days: u64) -> Result<Self, SchoolError> {
+        let mut records = vec![DayRecord {
+            day: 0,
+            total: school
+                .checked_total()
+                .ok_or(SchoolError::Overflow { day: 0 })?,
+            amount_of_fish_per_timer: school.amount_of_fish_per_timer().to_vec(),
+            growth_rate: None,
+        }];
+        for day in 1..=days {
+            let total = school
+                .checked_simulate_day()
+                .and_then(|_| school.checked_total())
+                .ok_or(SchoolError::Overflow { day })?;
+            let previous = records.last().unwrap().total;
+            records.push(DayRecord {
+                day,
+                total,
+                amount_of_fish_per_timer: school.amount_of_fish_per_timer().to_vec(),
+                growth_rate: (previous > 0).then(|| total as f64 / previous as f64),
+            });
+        }
+        Ok(PopulationReport {
+            asymptotic_growth_rate: school.rules().asymptotic_growth_rate(),
+            days: records,
+        })
+    }
+
+    pub fn deviation_from_asymptote(&self, record: &DayRecord) -> Option<f64> {
+        record
+            .growth_rate
+            .map(|growth_rate| growth_rate - self.asymptotic_growth_rate)
+    }
+
+    pub fn to_csv(&self) -> String {
+        let number_of_timers = self
+            .days
+            .first()
+            .map_or(0, |record| record.amount_of_fish_per_timer.len());
+        let mut csv = String::from("day,total");
+        for timer in 0..number_of_timers {
+            write!(csv, ",timer_{}", timer).unwrap();
+        }
+        csv.push_str(",growth_rate,deviation_from_asymptote\n");
+
+        for record in &self.days {
+            write!(csv, "{},{}", record.day, record.total).unwrap();
+            for amount in &record.amount_of_fish_per_timer {
+                write!(csv, ",{}", amount).unwrap();
+            }
+            writeln!(
+                csv,
+                ",{},{}",
+                format_rate(record.growth_rate, ""),
+                format_rate(self.deviation_from_asymptote(record), "")
+            )
+            .unwrap();
+        }
+        csv
+    }
+
+    pub fn to_json(&self) -> String {
+        let days: Vec<String> = self
+            .days
+            .iter()
+            .map(|record| {
+                let timers: Vec<String> = record
+                    .amount_of_fish_per_timer
+                    .iter()
+                    .map(|amount| amount.to_string())
+                    .collect();
+                format!(
+                    "{{\"day\":{},\"total\":{},\"amount_of_fish_per_timer\":[{}],\"growth_rate\":{},\"deviation_from_asymptote\":{}}}",
+                    record.day,
+                    record.total,
+                    timers.join(","),
+                    format_rate(record.growth_rate, "null"),
+                    format_rate(self.deviation_from_asymptote(record), "null")
+                )
+            })
+            .collect();
+        format!(
+            "{{\"asymptotic_growth_rate\":{:.6},\"days\":[{}]}}",
+            self.asymptotic_growth_rate,
+            days.join(",")
+        )
+    }
+}
+
+fn format_rate(rate: Option<f64>, missing: &str) -> String {
+    rate.map_or(missing.to_string(), |rate| format!("{:.6}", rate))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report() -> PopulationReport {
+        let school = SchoolOFish::new(vec![3, 4, 3, 1, 2]).unwrap();
+        PopulationReport::simulate(school, 2).unwrap()
+    }
+
+    #[test]
+    fn records_every_day() {
+        let report = report();
+        let totals: Vec<AmountOfFish> = report.days.iter().map(|record| record.total).collect();
+        assert_eq!(totals, vec![5, 5, 6]);
+        assert_eq!(
+            report.days[2].amount_of_fish_per_timer,
+            vec![1, 2, 1, 0, 0, 0, 1, 0, 1]
+        );
+        assert_eq!(report.days[0].growth_rate, None);
+        assert_eq!(report.days[2].growth_rate, Some(1.2));
+    }
+
+    #[test]
+    fn exports_csv() {
+        let csv = report().to_csv();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(
+            lines[0],
+            "day,total,timer_0,timer_1,timer_2,timer_3,timer_4,timer_5,timer_6,timer_7,timer_8,growth_rate,deviation_from_asymptote"
+        );
+        assert_eq!(lines[1], "0,5,0,1,1,2,1,0,0,0,0,,");
+        assert_eq!(lines[3], "2,6,1,2,1,0,0,0,1,0,1,1.200000,0.108976");
+    }
+
+    #[test]
+    fn exports_json() {
+        let json = report().to_json();
+        assert!(json
+            .starts_with("{\"asymptotic_growth_rate\":1.091024,\"days\":[{\"day\":0,\"total\":5,"));
+        assert!(json.contains("\"growth_rate\":null"));
+        assert!(json.ends_with("\"growth_rate\":1.200000,\"deviation_from_asymptote\":0.108976}]}"));
+    }
+}
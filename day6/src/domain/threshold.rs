@@ -0,0 +1,123 @@
+use crate::domain::arithmetic::{Arithmetic, Exact};
+use crate::domain::{BigNumber, Matrix, SchoolError, SchoolOFish};
+
+impl SchoolOFish {
+    // Bounds the day by squaring the transition matrix, then binary searches by applying the
+    // powers from large to small, relying on a population that never shrinks
+    pub fn first_day_reaching(&self, threshold: &BigNumber) -> Result<Option<u64>, SchoolError> {
+        if !self.limits().is_unlimited() {
+            return self.first_limited_day_reaching(threshold);
+        }
+        let state: Vec<BigNumber> = self
+            .amount_of_fish_per_timer()
+            .iter()
+            .map(|&amount| Exact.amount(amount))
+            .collect();
+        if total(&state) >= *threshold {
+            return Ok(Some(0));
+        }
+        if self.total() == 0 || self.rules().offspring_per_spawn == 0 {
+            return Ok(None);
+        }
+
+        let mut powers = vec![Matrix::transition(self.rules()).convert(&Exact)];
+        while total(&powers.last().unwrap().apply(&state, &Exact)) < *threshold {
+            let last = powers.last().unwrap();
+            powers.push(last.multiply(last, &Exact));
+        }
+
+        let mut state = state;
+        let mut day = 0;
+        for (exponent, power) in powers.iter().enumerate().rev() {
+            let candidate = power.apply(&state, &Exact);
+            if total(&candidate) < *threshold {
+                state = candidate;
+                day += 1 << exponent;
+            }
+        }
+        Ok(Some(day + 1))
+    }
+
+    pub fn days_until_doubled(&self) -> Result<Option<u64>, SchoolError> {
+        let total = Exact.amount(self.total());
+        self.first_day_reaching(&Exact.add(&total, &total))
+    }
+}
+
+fn total(state: &[BigNumber]) -> BigNumber {
+    state
+        .iter()
+        .fold(BigNumber::default(), |total, amount| &total + amount)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{AmountOfFish, SpawnRules};
+
+    fn first_day_by_simulation(mut school: SchoolOFish, threshold: AmountOfFish) -> u64 {
+        let mut day = 0;
+        while school.total() < threshold {
+            school.simulate_day();
+            day += 1;
+        }
+        day
+    }
+
+    #[test]
+    fn matches_simulation() {
+        for threshold in [1, 5, 6, 26, 5934, 1_000_000, 26_984_457_539] {
+            let school = SchoolOFish::new(vec![3, 4, 3, 1, 2]).unwrap();
+            let expected =
+                first_day_by_simulation(SchoolOFish::new(vec![3, 4, 3, 1, 2]).unwrap(), threshold);
+            assert_eq!(
+                school.first_day_reaching(&BigNumber::from(threshold)),
+                Ok(Some(expected))
+            );
+        }
+    }
+
+    #[test]
+    fn matches_simulation_with_custom_rules() {
+        let rules = SpawnRules {
+            cycle_length: 4,
+            first_cycle_delay: 3,
+            offspring_per_spawn: 2,
+        };
+        let school = SchoolOFish::with_rules(vec![6, 0], rules).unwrap();
+        let expected = first_day_by_simulation(
+            SchoolOFish::with_rules(vec![6, 0], rules).unwrap(),
+            123_456_789,
+        );
+        assert_eq!(
+            school.first_day_reaching(&BigNumber::from(123_456_789)),
+            Ok(Some(expected))
+        );
+    }
+
+    #[test]
+    fn doubling() {
+        let school = SchoolOFish::new(vec![3, 4, 3, 1, 2]).unwrap();
+        let expected = first_day_by_simulation(SchoolOFish::new(vec![3, 4, 3, 1, 2]).unwrap(), 10);
+        assert_eq!(school.days_until_doubled(), Ok(Some(expected)));
+    }
+
+    #[test]
+    fn never_reached_without_offspring() {
+        let rules = SpawnRules {
+            offspring_per_spawn: 0,
+            ..SpawnRules::default()
+        };
+        let school = SchoolOFish::with_rules(vec![3, 4], rules).unwrap();
+        assert_eq!(school.first_day_reaching(&BigNumber::from(3)), Ok(None));
+    }
+
+    #[test]
+    fn huge_threshold() {
+        let school = SchoolOFish::new(vec![3, 4, 3, 1, 2]).unwrap();
+        let threshold = &BigNumber::from(u64::MAX) * &BigNumber::from(u64::MAX);
+        let day = school.first_day_reaching(&threshold).unwrap().unwrap();
+        assert!(school.project(day, &Exact).unwrap() >= threshold);
+        assert!(school.project(day - 1, &Exact).unwrap() < threshold);
+    }
+}
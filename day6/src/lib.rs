@@ -1,11 +1,102 @@
-use crate::domain::{AmountOfFish, SchoolOFish};
+pub use crate::domain::{
+    AmountOfFish, Arithmetic, BigNumber, Checked, DayRecord, Exact, Fish, FishId, FishLifetime,
+    IndividualSchool, Limits, Matrix, Modular, Mortality, Native, PopulationReport, SchoolError,
+    SchoolOFish, SpawnRules,
+};
 
 mod domain;
 
 pub fn number_of_fish(after_days: u32, contents: &str) -> AmountOfFish {
-    let mut school = contents.parse::<SchoolOFish>().unwrap();
+    number_of_fish_with_rules(after_days, contents, SpawnRules::default()).unwrap()
+}
+
+pub fn number_of_fish_with_rules(
+    after_days: u32,
+    contents: &str,
+    rules: SpawnRules,
+) -> Result<AmountOfFish, SchoolError> {
+    let mut school = SchoolOFish::parse_with_rules(contents, rules)?;
     (0..after_days).for_each(|_| school.simulate_day());
-    school.total()
+    Ok(school.total())
+}
+
+pub fn number_of_fish_with_limits(
+    after_days: u32,
+    contents: &str,
+    rules: SpawnRules,
+    limits: Limits,
+) -> Result<AmountOfFish, SchoolError> {
+    let mut school = SchoolOFish::parse_with_rules(contents, rules)?.with_limits(limits)?;
+    (0..after_days).for_each(|_| school.simulate_day());
+    Ok(school.total())
+}
+
+pub fn number_of_fish_fast_forward(
+    after_days: u64,
+    contents: &str,
+    rules: SpawnRules,
+) -> Result<AmountOfFish, SchoolError> {
+    let mut school = SchoolOFish::parse_with_rules(contents, rules)?;
+    school.fast_forward(after_days)?;
+    Ok(school.total())
+}
+
+pub fn number_of_fish_checked(
+    after_days: u64,
+    contents: &str,
+    rules: SpawnRules,
+) -> Result<AmountOfFish, SchoolError> {
+    let mut school = SchoolOFish::parse_with_rules(contents, rules)?;
+    for day in 1..=after_days {
+        school
+            .checked_simulate_day()
+            .and_then(|_| school.checked_total())
+            .ok_or(SchoolError::Overflow { day })?;
+    }
+    Ok(school.total())
+}
+
+pub fn number_of_fish_exact(
+    after_days: u64,
+    contents: &str,
+    rules: SpawnRules,
+) -> Result<BigNumber, SchoolError> {
+    let school = SchoolOFish::parse_with_rules(contents, rules)?;
+    school.project(after_days, &Exact)
+}
+
+pub fn number_of_fish_modulo(
+    after_days: u64,
+    modulus: u64,
+    contents: &str,
+    rules: SpawnRules,
+) -> Result<u64, SchoolError> {
+    let modular = Modular::new(modulus)?;
+    let school = SchoolOFish::parse_with_rules(contents, rules)?;
+    school.project(after_days, &modular)
+}
+
+pub fn population_report(
+    after_days: u64,
+    contents: &str,
+    rules: SpawnRules,
+) -> Result<PopulationReport, SchoolError> {
+    let school = SchoolOFish::parse_with_rules(contents, rules)?;
+    PopulationReport::simulate(school, after_days)
+}
+
+pub fn first_day_reaching(
+    threshold: AmountOfFish,
+    contents: &str,
+    rules: SpawnRules,
+) -> Result<Option<u64>, SchoolError> {
+    let school = SchoolOFish::parse_with_rules(contents, rules)?;
+    school.first_day_reaching(&BigNumber::from(threshold))
+}
+
+pub fn days_until_doubled(contents: &str, rules: SpawnRules) -> Result<Option<u64>, SchoolError> {
+    let school = SchoolOFish::parse_with_rules(contents, rules)?;
+    school.days_until_doubled()
 }
 
 #[cfg(test)]
@@ -39,4 +130,99 @@ mod tests {
         let result = number_of_fish(256, input);
         assert_eq!(result, 1600306001288);
     }
+
+    #[test]
+    fn number_of_fish_with_out_of_range_timer() {
+        let result = number_of_fish_with_rules(18, "3,4,9,1,2\n", SpawnRules::default());
+        assert_eq!(
+            result,
+            Err(SchoolError::TimerOutOfRange { timer: 9, max: 8 })
+        );
+    }
+
+    #[test]
+    fn part_1_with_limits() {
+        let input = include_str!("../input.txt");
+        let rules = SpawnRules::default();
+        assert_eq!(
+            number_of_fish_with_limits(80, input, rules, Limits::default()),
+            Ok(352195)
+        );
+        let limits = Limits {
+            mortality: Mortality::MaximumAge(40),
+            carrying_capacity: Some(100_000),
+        };
+        let result = number_of_fish_with_limits(80, input, rules, limits).unwrap();
+        assert!(result <= 100_000);
+    }
+
+    #[test]
+    fn part_2_fast_forward() {
+        let input = include_str!("../input.txt");
+        let result = number_of_fish_fast_forward(256, input, SpawnRules::default());
+        assert_eq!(result, Ok(1600306001288));
+    }
+
+    #[test]
+    fn fast_forward_reports_overflow() {
+        let rules = SpawnRules::default();
+        assert_eq!(
+            number_of_fish_fast_forward(1000, "3,4,3,1,2", rules),
+            Err(SchoolError::Overflow { day: 1000 })
+        );
+        assert_eq!(
+            number_of_fish_fast_forward(400, "3,4,3,1,2", rules),
+            number_of_fish_checked(400, "3,4,3,1,2", rules)
+        );
+    }
+
+    #[test]
+    fn checked_reports_day_of_overflow() {
+        let input = include_str!("../input.txt");
+        let rules = SpawnRules::default();
+        assert_eq!(number_of_fish_checked(256, input, rules), Ok(1600306001288));
+        let overflow_day = match number_of_fish_checked(1000, input, rules) {
+            Err(SchoolError::Overflow { day }) => day,
+            result => panic!("expected overflow, got {:?}", result),
+        };
+        assert!(number_of_fish_checked(overflow_day - 1, input, rules).is_ok());
+    }
+
+    #[test]
+    fn exact_and_modular_agree() {
+        let input = include_str!("../input.txt");
+        let rules = SpawnRules::default();
+        assert_eq!(
+            number_of_fish_exact(256, input, rules).unwrap().to_string(),
+            "1600306001288"
+        );
+        let exact = number_of_fish_exact(1000, input, rules).unwrap();
+        let modulo = number_of_fish_modulo(1000, 1_000_000_007, input, rules).unwrap();
+        assert_eq!(exact.divide_small(1_000_000_007).unwrap().1 as u64, modulo);
+    }
+
+    #[test]
+    fn modular_for_huge_horizon() {
+        let rules = SpawnRules::default();
+        let result = number_of_fish_modulo(1_000_000_000_000, 1_000_000_007, "3,4,3,1,2\n", rules);
+        assert!(result.unwrap() < 1_000_000_007);
+        assert_eq!(
+            number_of_fish_modulo(10, 0, "3,4,3,1,2\n", rules),
+            Err(SchoolError::InvalidModulus)
+        );
+    }
+
+    #[test]
+    fn first_day_reaching_part_2() {
+        let input = include_str!("../input.txt");
+        let rules = SpawnRules::default();
+        assert_eq!(
+            first_day_reaching(1600306001288, input, rules),
+            Ok(Some(256))
+        );
+        assert_eq!(
+            first_day_reaching(1600306001289, input, rules),
+            Ok(Some(257))
+        );
+    }
 }
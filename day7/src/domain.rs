@@ -1,45 +1,155 @@
+use std::fmt;
+use std::ops::RangeInclusive;
 use std::str::FromStr;
 
-pub struct CrabPositions {
-    positions: Vec<i32>,
+mod curve;
+mod fuel_cost;
+mod plane;
+mod sorted_positions;
+
+pub use curve::FuelCurve;
+pub use fuel_cost::{Coefficients, FuelCost, Linear, Quadratic, Triangular};
+pub use plane::{GeometricMedian, PlanarAlignment, PlanarCrabPositions, Point};
+use sorted_positions::SortedPositions;
+
+pub type Position = i64;
+pub type Fuel = u128;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum CrabError {
+    NoCrabs,
+    InvalidPosition(String),
+    FuelOverflow { target: Position },
+    PlanarFuelOverflow { target: Point },
 }
 
-impl CrabPositions {
-    pub fn required_fuel_to_reach_simple(&self, target: i32) -> i32 {
-        self.positions.iter().map(|&pos| (pos - target).abs()).sum()
+impl fmt::Display for CrabError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CrabError::NoCrabs => write!(f, "expected at least one crab position"),
+            CrabError::InvalidPosition(position) => {
+                write!(f, "expected a crab position, found {:?}", position)
+            }
+            CrabError::FuelOverflow { target } => {
+                write!(f, "fuel to reach {} does not fit in 128 bits", target)
+            }
+            CrabError::PlanarFuelOverflow { target } => write!(
+                f,
+                "fuel to reach {}:{} does not fit in 128 bits",
+                target.x, target.y
+            ),
+        }
     }
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Alignment {
+    pub target: Position,
+    pub fuel: Fuel,
+}
+
+pub struct CrabPositions {
+    positions: Vec<Position>,
+}
 
-    pub fn required_fuel_to_reach_power(&self, target: i32) -> i32 {
+impl CrabPositions {
+    pub fn required_fuel_to_reach<C: FuelCost>(
+        &self,
+        target: Position,
+        cost: &C,
+    ) -> Result<Fuel, CrabError> {
         self.positions
             .iter()
-            .map(|&pos| {
-                let steps = (pos - target).abs();
-                (steps * (steps + 1)) / 2 // Gauss formula for 1 + 2 + 3 + ... steps
+            .try_fold(0, |total: Fuel, &pos| {
+                total.checked_add(cost.cost(pos.abs_diff(target))?)
             })
-            .sum()
+            .ok_or(CrabError::FuelOverflow { target })
+    }
+
+    pub fn optimal_alignment<C: FuelCost>(&self, cost: &C) -> Result<Alignment, CrabError> {
+        if self.positions.is_empty() {
+            return Err(CrabError::NoCrabs);
+        }
+        let candidates = cost.candidates(&self.positions);
+        match cost.coefficients() {
+            Some(coefficients) => {
+                let sorted = SortedPositions::new(&self.positions);
+                match candidates {
+                    Some(candidates) => {
+                        cheapest(candidates, |target| sorted.fuel(target, &coefficients))
+                    }
+                    None => sorted.optimal_alignment(&coefficients),
+                }
+            }
+            None => self.optimal_alignment_by_brute_force(cost),
+        }
     }
 
-    pub fn median(&self) -> i32 {
-        statistical::median(&self.positions)
+    pub fn optimal_alignment_by_brute_force<C: FuelCost>(
+        &self,
+        cost: &C,
+    ) -> Result<Alignment, CrabError> {
+        cheapest(self.min()?..=self.max()?, |target| {
+            self.required_fuel_to_reach(target, cost)
+        })
     }
 
-    pub fn min(&self) -> i32 {
-        *self.positions.iter().min().unwrap()
+    pub fn is_empty(&self) -> bool {
+        self.positions.is_empty()
     }
 
-    pub fn max(&self) -> i32 {
-        *self.positions.iter().max().unwrap()
+    pub fn median(&self) -> Result<Position, CrabError> {
+        if self.positions.is_empty() {
+            return Err(CrabError::NoCrabs);
+        }
+        Ok(statistical::median(&self.positions))
     }
+
+    pub fn min(&self) -> Result<Position, CrabError> {
+        self.positions
+            .iter()
+            .copied()
+            .min()
+            .ok_or(CrabError::NoCrabs)
+    }
+
+    pub fn max(&self) -> Result<Position, CrabError> {
+        self.positions
+            .iter()
+            .copied()
+            .max()
+            .ok_or(CrabError::NoCrabs)
+    }
+}
+
+// The first target with the lowest fuel
+fn cheapest(
+    targets: RangeInclusive<Position>,
+    fuel: impl Fn(Position) -> Result<Fuel, CrabError>,
+) -> Result<Alignment, CrabError> {
+    let mut best: Option<Alignment> = None;
+    for target in targets {
+        let fuel = fuel(target)?;
+        if best.is_none_or(|best| fuel < best.fuel) {
+            best = Some(Alignment { target, fuel });
+        }
+    }
+    best.ok_or(CrabError::NoCrabs)
 }
 
 impl FromStr for CrabPositions {
-    type Err = ();
+    type Err = CrabError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let positions = s
             .split(',')
-            .filter_map(|p| p.trim().parse::<i32>().ok())
-            .collect();
+            .map(str::trim)
+            .filter(|p| !p.is_empty())
+            .map(|p| {
+                p.parse::<Position>()
+                    .map_err(|_| CrabError::InvalidPosition(p.to_string()))
+            })
+            .collect::<Result<Vec<Position>, CrabError>>()?;
         Ok(CrabPositions { positions })
     }
 }
@@ -48,12 +158,152 @@ impl FromStr for CrabPositions {
 mod tests {
     use super::*;
 
+    fn example() -> CrabPositions {
+        CrabPositions {
+            positions: vec![16, 1, 2, 0, 4, 2, 7, 1, 2, 14],
+        }
+    }
+
     #[test]
     fn test_calculate_power() {
+        let positions = example();
+        assert_eq!(positions.required_fuel_to_reach(5, &Triangular), Ok(168));
+        assert_eq!(positions.required_fuel_to_reach(2, &Triangular), Ok(206));
+    }
+
+    #[test]
+    fn test_optimal_alignment() {
+        let positions = example();
+        assert_eq!(
+            positions.optimal_alignment(&Linear),
+            Ok(Alignment {
+                target: 2,
+                fuel: 37
+            })
+        );
+        assert_eq!(
+            positions.optimal_alignment(&Triangular),
+            Ok(Alignment {
+                target: 5,
+                fuel: 168
+            })
+        );
+        assert_eq!(
+            positions.optimal_alignment(&Quadratic),
+            Ok(Alignment {
+                target: 5,
+                fuel: 291
+            })
+        );
+    }
+
+    #[test]
+    fn test_fast_search_equals_brute_force() {
+        let mut seed: u64 = 7;
+        for length in 1..40 {
+            let positions = CrabPositions {
+                positions: (0..length)
+                    .map(|_| {
+                        seed = seed
+                            .wrapping_mul(6364136223846793005)
+                            .wrapping_add(1442695040888963407);
+                        (seed >> 33) as Position % 200 - 50
+                    })
+                    .collect(),
+            };
+            assert_eq!(
+                positions.optimal_alignment(&Linear),
+                positions.optimal_alignment_by_brute_force(&Linear)
+            );
+            assert_eq!(
+                positions.optimal_alignment(&Triangular),
+                positions.optimal_alignment_by_brute_force(&Triangular)
+            );
+            assert_eq!(
+                positions.optimal_alignment(&Quadratic),
+                positions.optimal_alignment_by_brute_force(&Quadratic)
+            );
+        }
+    }
+
+    #[test]
+    fn test_user_supplied_cost() {
+        let positions = example();
+        let doubled = |distance: u64| Some(2 * distance as Fuel);
+        assert_eq!(
+            positions.optimal_alignment(&doubled),
+            Ok(Alignment {
+                target: 2,
+                fuel: 74
+            })
+        );
+        let empty = CrabPositions { positions: vec![] };
+        assert_eq!(empty.optimal_alignment(&Linear), Err(CrabError::NoCrabs));
+        assert_eq!(empty.min(), Err(CrabError::NoCrabs));
+        assert_eq!(empty.max(), Err(CrabError::NoCrabs));
+    }
+
+    #[test]
+    fn test_far_apart_positions() {
         let positions = CrabPositions {
-            positions: vec![16, 1, 2, 0, 4, 2, 7, 1, 2, 14],
+            positions: vec![0, 100_000],
+        };
+        assert_eq!(
+            positions.required_fuel_to_reach(0, &Triangular),
+            Ok(5_000_050_000)
+        );
+        let positions = CrabPositions {
+            positions: vec![Position::MIN, Position::MAX],
         };
-        assert_eq!(positions.required_fuel_to_reach_power(5), 168);
-        assert_eq!(positions.required_fuel_to_reach_power(2), 206);
+        assert_eq!(
+            positions
+                .optimal_alignment(&Linear)
+                .map(|alignment| alignment.fuel),
+            Ok(u64::MAX as Fuel)
+        );
+        assert_eq!(
+            positions.required_fuel_to_reach(Position::MIN, &Triangular),
+            Ok((u64::MAX as Fuel) * (u64::MAX as Fuel + 1) / 2)
+        );
+        let positions = CrabPositions {
+            positions: vec![Position::MIN, Position::MAX, Position::MAX],
+        };
+        assert_eq!(
+            positions.required_fuel_to_reach(Position::MIN, &Quadratic),
+            Err(CrabError::FuelOverflow {
+                target: Position::MIN
+            })
+        );
+        let alignment = positions.optimal_alignment(&Quadratic).unwrap();
+        assert_eq!(
+            positions.required_fuel_to_reach(alignment.target, &Quadratic),
+            Ok(alignment.fuel)
+        );
+    }
+
+    #[test]
+    fn test_large_clustered_positions_equal_brute_force() {
+        let fleets = [
+            vec![Position::MAX, Position::MAX],
+            vec![Position::MIN, Position::MIN + 3],
+            (0..21)
+                .map(|index| 3_000_000_000_000_000_000 + index % 3)
+                .collect(),
+        ];
+        for fleet in fleets {
+            let positions = CrabPositions { positions: fleet };
+            assert_eq!(
+                positions.optimal_alignment(&Linear),
+                positions.optimal_alignment_by_brute_force(&Linear)
+            );
+            assert_eq!(
+                positions.optimal_alignment(&Triangular),
+                positions.optimal_alignment_by_brute_force(&Triangular)
+            );
+            assert_eq!(
+                positions.optimal_alignment(&Quadratic),
+                positions.optimal_alignment_by_brute_force(&Quadratic)
+            );
+        }
     }
 }
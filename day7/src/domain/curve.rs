@@ -0,0 +1,201 @@
+use crate::domain::sorted_positions::SortedPositions;
+use crate::domain::{Alignment, CrabError, CrabPositions, Fuel, FuelCost, Position};
+use std::fmt::Write;
+use std::ops::RangeInclusive;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FuelCurve {
+    pub points: Vec<Alignment>,
+}
+
+impl CrabPositions {
+    pub fn fuel_curve<C: FuelCost>(
+        &self,
+        targets: RangeInclusive<Position>,
+        cost: &C,
+    ) -> Result<FuelCurve, CrabError> {
+        let sorted = cost
+            .coefficients()
+            .map(|coefficients| (SortedPositions::new(&self.positions), coefficients));
+        let points = targets
+            .map(|target| {
+                let fuel = match &sorted {
+                    Some((sorted, coefficients)) => sorted.fuel(target, coefficients),
+                    None => self.required_fuel_to_reach(target, cost),
+                }?;
+                Ok(Alignment { target, fuel })
+            })
+            .collect::<Result<Vec<Alignment>, CrabError>>()?;
+        Ok(FuelCurve { points })
+    }
+}
+
+impl FuelCurve {
+    pub fn minimum(&self) -> Option<Alignment> {
+        self.points.iter().copied().min_by_key(|point| point.fuel)
+    }
+
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("target,fuel\n");
+        for point in &self.points {
+            writeln!(csv, "{},{}", point.target, point.fuel).unwrap();
+        }
+        csv
+    }
+
+    // Each column shows the lowest fuel of the targets it covers, the minimum is drawn as 'o'
+    pub fn to_ascii_plot(&self, width: usize, height: usize) -> String {
+        let minimum = match self.minimum() {
+            Some(minimum) if width > 0 && height > 0 => minimum,
+            _ => return String::new(),
+        };
+        let columns = width.min(self.points.len());
+        let lowest: Vec<Alignment> = (0..columns)
+            .map(|column| {
+                let from = column * self.points.len() / columns;
+                let to = (column + 1) * self.points.len() / columns;
+                *self.points[from..to]
+                    .iter()
+                    .min_by_key(|point| point.fuel)
+                    .unwrap()
+            })
+            .collect();
+        let maximum = self.points.iter().map(|point| point.fuel).max().unwrap();
+        let row_of = |fuel: Fuel| match maximum - minimum.fuel {
+            0 => height - 1,
+            spread => {
+                ((maximum - fuel) as f64 / spread as f64 * (height - 1) as f64).round() as usize
+            }
+        };
+
+        let label_width = maximum
+            .to_string()
+            .len()
+            .max(minimum.fuel.to_string().len());
+        let mut plot = String::new();
+        for row in 0..height {
+            let label = match row {
+                0 => maximum.to_string(),
+                row if row == height - 1 => minimum.fuel.to_string(),
+                _ => String::new(),
+            };
+            let cells: String = lowest
+                .iter()
+                .map(|point| match row_of(point.fuel) == row {
+                    true if *point == minimum => 'o',
+                    true => '*',
+                    false => ' ',
+                })
+                .collect();
+            writeln!(plot, "{:>label_width$} |{}", label, cells.trim_end()).unwrap();
+        }
+        writeln!(plot, "{:>label_width$} +{}", "", "-".repeat(columns)).unwrap();
+        let first = self.points.first().unwrap().target.to_string();
+        let last = self.points.last().unwrap().target.to_string();
+        let gap = columns.saturating_sub(first.len() + last.len()).max(1);
+        writeln!(
+            plot,
+            "{:>label_width$}  {}{}{}",
+            "",
+            first,
+            " ".repeat(gap),
+            last
+        )
+        .unwrap();
+        writeln!(
+            plot,
+            "minimum: target {}, fuel {}",
+            minimum.target, minimum.fuel
+        )
+        .unwrap();
+        plot
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::{CrabError, CrabPositions, Fuel, Linear, Position, Triangular};
+
+    fn example() -> CrabPositions {
+        "16,1,2,0,4,2,7,1,2,14".parse().unwrap()
+    }
+
+    #[test]
+    fn curve_with_every_cost_model() {
+        let positions = example();
+        let curve = positions.fuel_curve(0..=16, &Triangular).unwrap();
+        assert_eq!(curve.points.len(), 17);
+        assert_eq!(curve.points[2].fuel, 206);
+        assert_eq!(
+            curve.minimum(),
+            positions.optimal_alignment(&Triangular).ok()
+        );
+        let doubled = positions
+            .fuel_curve(0..=16, &|distance: u64| Some(2 * distance as Fuel))
+            .unwrap();
+        let linear = positions.fuel_curve(0..=16, &Linear).unwrap();
+        for (doubled, linear) in doubled.points.iter().zip(&linear.points) {
+            assert_eq!(doubled.fuel, 2 * linear.fuel);
+        }
+    }
+
+    #[test]
+    fn exports_csv() {
+        let csv = example().fuel_curve(1..=3, &Linear).unwrap().to_csv();
+        assert_eq!(csv, "target,fuel\n1,41\n2,37\n3,39\n");
+    }
+
+    #[test]
+    fn exports_ascii_plot() {
+        let plot = example()
+            .fuel_curve(0..=16, &Linear)
+            .unwrap()
+            .to_ascii_plot(17, 4);
+        let lines: Vec<&str> = plot.lines().collect();
+        assert_eq!(
+            lines,
+            vec![
+                "111 |               **",
+                "    |           ****",
+                "    |       ****",
+                " 37 |**o****",
+                "    +-----------------",
+                "     0              16",
+                "minimum: target 2, fuel 37",
+            ]
+        );
+    }
+
+    #[test]
+    fn curve_of_large_clustered_positions() {
+        let positions: CrabPositions = (0..21)
+            .map(|index: Position| (3_000_000_000_000_000_000 + index % 3).to_string())
+            .collect::<Vec<String>>()
+            .join(",")
+            .parse()
+            .unwrap();
+        let targets = 3_000_000_000_000_000_000..=3_000_000_000_000_000_002;
+        let curve = positions.fuel_curve(targets.clone(), &Triangular).unwrap();
+        for (point, target) in curve.points.iter().zip(targets) {
+            assert_eq!(
+                Ok(point.fuel),
+                positions.required_fuel_to_reach(target, &Triangular)
+            );
+        }
+    }
+
+    #[test]
+    fn reports_overflow() {
+        let positions: CrabPositions =
+            format!("{},{},{}", Position::MIN, Position::MAX, Position::MAX)
+                .parse()
+                .unwrap();
+        let overflowing = |distance: u64| (distance as Fuel).checked_mul(distance as Fuel);
+        assert_eq!(
+            positions.fuel_curve(Position::MIN..=Position::MIN, &overflowing),
+            Err(CrabError::FuelOverflow {
+                target: Position::MIN
+            })
+        );
+    }
+}
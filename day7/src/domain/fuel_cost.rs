@@ -0,0 +1,95 @@
+use crate::domain::{Fuel, Position};
+use std::ops::RangeInclusive;
+
+// Fuel for a distance d is (linear * d + quadratic * d^2) / divisor
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Coefficients {
+    pub linear: i128,
+    pub quadratic: i128,
+    pub divisor: i128,
+}
+
+pub trait FuelCost {
+    // None when the fuel does not fit
+    fn cost(&self, distance: u64) -> Option<Fuel>;
+
+    // Costs with non-negative coefficients are convex, which allows a fast search
+    fn coefficients(&self) -> Option<Coefficients> {
+        None
+    }
+
+    // Targets known to contain the optimum, when it can be found without searching
+    fn candidates(&self, _positions: &[Position]) -> Option<RangeInclusive<Position>> {
+        None
+    }
+}
+
+// Every step costs one unit of fuel
+pub struct Linear;
+
+// Every step costs one more than the previous one
+pub struct Triangular;
+
+pub struct Quadratic;
+
+impl FuelCost for Linear {
+    fn cost(&self, distance: u64) -> Option<Fuel> {
+        Some(distance as Fuel)
+    }
+
+    fn coefficients(&self) -> Option<Coefficients> {
+        Some(Coefficients {
+            linear: 1,
+            quadratic: 0,
+            divisor: 1,
+        })
+    }
+}
+
+impl FuelCost for Triangular {
+    fn cost(&self, distance: u64) -> Option<Fuel> {
+        let distance = distance as Fuel;
+        // Gauss formula for 1 + 2 + 3 + ... steps
+        Some(distance.checked_mul(distance + 1)? / 2)
+    }
+
+    fn coefficients(&self) -> Option<Coefficients> {
+        Some(Coefficients {
+            linear: 1,
+            quadratic: 1,
+            divisor: 2,
+        })
+    }
+
+    // The continuous optimum lies within half a step of the mean
+    fn candidates(&self, positions: &[Position]) -> Option<RangeInclusive<Position>> {
+        if positions.is_empty() {
+            return None;
+        }
+        let sum: i128 = positions.iter().map(|&position| position as i128).sum();
+        let count = positions.len() as i128;
+        let floor = sum.div_euclid(count) as Position;
+        let ceiling = floor + (sum.rem_euclid(count) != 0) as Position;
+        Some(floor.saturating_sub(1)..=ceiling.saturating_add(1))
+    }
+}
+
+impl FuelCost for Quadratic {
+    fn cost(&self, distance: u64) -> Option<Fuel> {
+        (distance as Fuel).checked_mul(distance as Fuel)
+    }
+
+    fn coefficients(&self) -> Option<Coefficients> {
+        Some(Coefficients {
+            linear: 0,
+            quadratic: 1,
+            divisor: 1,
+        })
+    }
+}
+
+impl<F: Fn(u64) -> Option<Fuel>> FuelCost for F {
+    fn cost(&self, distance: u64) -> Option<Fuel> {
+        self(distance)
+    }
+}
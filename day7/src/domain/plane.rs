@@ -0,0 +1,271 @@
+use crate::domain::{CrabError, CrabPositions, Fuel, FuelCost, Linear, Position};
+use std::str::FromStr;
+
+const WEISZFELD_ITERATIONS: usize = 1000;
+const WEISZFELD_TOLERANCE: f64 = 1e-9;
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Point {
+    pub x: Position,
+    pub y: Position,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct PlanarAlignment {
+    pub target: Point,
+    pub fuel: Fuel,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct GeometricMedian {
+    pub x: f64,
+    pub y: f64,
+    pub distance: f64,
+}
+
+pub struct PlanarCrabPositions {
+    points: Vec<Point>,
+}
+
+impl PlanarCrabPositions {
+    pub fn points(&self) -> &[Point] {
+        &self.points
+    }
+
+    // A cost applied to each axis separately is optimal when each axis is
+    pub fn optimal_alignment_per_axis<C: FuelCost>(
+        &self,
+        cost: &C,
+    ) -> Result<PlanarAlignment, CrabError> {
+        let horizontal = CrabPositions {
+            positions: self.points.iter().map(|point| point.x).collect(),
+        }
+        .optimal_alignment(cost)?;
+        let vertical = CrabPositions {
+            positions: self.points.iter().map(|point| point.y).collect(),
+        }
+        .optimal_alignment(cost)?;
+        let target = Point {
+            x: horizontal.target,
+            y: vertical.target,
+        };
+        Ok(PlanarAlignment {
+            target,
+            fuel: horizontal
+                .fuel
+                .checked_add(vertical.fuel)
+                .ok_or(CrabError::PlanarFuelOverflow { target })?,
+        })
+    }
+
+    pub fn manhattan_alignment(&self) -> Result<PlanarAlignment, CrabError> {
+        self.optimal_alignment_per_axis(&Linear)
+    }
+
+    // Weiszfeld iteration, starting from the centroid. It converges slowly towards a crab, so the
+    // crab nearest to the estimate is tested for optimality first.
+    pub fn geometric_median(&self) -> Result<GeometricMedian, CrabError> {
+        if self.points.is_empty() {
+            return Err(CrabError::NoCrabs);
+        }
+        let points: Vec<(f64, f64)> = self
+            .points
+            .iter()
+            .map(|point| (point.x as f64, point.y as f64))
+            .collect();
+        let count = points.len() as f64;
+        let mut estimate = (
+            points.iter().map(|point| point.0).sum::<f64>() / count,
+            points.iter().map(|point| point.1).sum::<f64>() / count,
+        );
+        for _ in 0..WEISZFELD_ITERATIONS {
+            let nearest = *points
+                .iter()
+                .min_by(|a, b| euclidean(**a, estimate).total_cmp(&euclidean(**b, estimate)))
+                .unwrap();
+            if is_optimal_crab(&points, nearest) {
+                estimate = nearest;
+                break;
+            }
+            let (mut x, mut y, mut weights) = (0.0, 0.0, 0.0);
+            for point in &points {
+                let distance = euclidean(*point, estimate);
+                // A crab at the estimate has no defined weight, the others pull it away if needed
+                if distance > 0.0 {
+                    x += point.0 / distance;
+                    y += point.1 / distance;
+                    weights += 1.0 / distance;
+                }
+            }
+            if weights == 0.0 {
+                break;
+            }
+            let next = (x / weights, y / weights);
+            let moved = euclidean(next, estimate);
+            estimate = next;
+            if moved <= WEISZFELD_TOLERANCE * (1.0 + estimate.0.abs() + estimate.1.abs()) {
+                break;
+            }
+        }
+        Ok(GeometricMedian {
+            x: estimate.0,
+            y: estimate.1,
+            distance: points.iter().map(|&point| euclidean(point, estimate)).sum(),
+        })
+    }
+}
+
+// A crab is the geometric median when the other crabs, each pulling with a unit vector, pull no
+// harder than the number of crabs at that position
+fn is_optimal_crab(points: &[(f64, f64)], crab: (f64, f64)) -> bool {
+    let (mut pull_x, mut pull_y, mut crabs_at) = (0.0, 0.0, 0.0);
+    for &point in points {
+        let distance = euclidean(point, crab);
+        if distance == 0.0 {
+            crabs_at += 1.0;
+        } else {
+            pull_x += (point.0 - crab.0) / distance;
+            pull_y += (point.1 - crab.1) / distance;
+        }
+    }
+    pull_x.hypot(pull_y) <= crabs_at
+}
+
+fn euclidean(a: (f64, f64), b: (f64, f64)) -> f64 {
+    (a.0 - b.0).hypot(a.1 - b.1)
+}
+
+// Accepts x:y pairs, a plain integer is a crab at y = 0
+impl FromStr for PlanarCrabPositions {
+    type Err = CrabError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let points = s
+            .split(',')
+            .map(str::trim)
+            .filter(|p| !p.is_empty())
+            .map(|p| {
+                let (x, y) = p.split_once(':').unwrap_or((p, "0"));
+                match (x.trim().parse(), y.trim().parse()) {
+                    (Ok(x), Ok(y)) => Ok(Point { x, y }),
+                    _ => Err(CrabError::InvalidPosition(p.to_string())),
+                }
+            })
+            .collect::<Result<Vec<Point>, CrabError>>()?;
+        Ok(PlanarCrabPositions { points })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{Quadratic, Triangular};
+
+    #[test]
+    fn parses_pairs_and_integers() {
+        let positions: PlanarCrabPositions = "1:2, -3:4,5\n".parse().unwrap();
+        assert_eq!(
+            positions.points(),
+            &[
+                Point { x: 1, y: 2 },
+                Point { x: -3, y: 4 },
+                Point { x: 5, y: 0 }
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_malformed_pairs() {
+        for malformed in ["1:x", "1:2:3", ":4", "x"] {
+            assert_eq!(
+                format!("0:0,{}", malformed)
+                    .parse::<PlanarCrabPositions>()
+                    .err(),
+                Some(CrabError::InvalidPosition(malformed.to_string()))
+            );
+        }
+    }
+
+    #[test]
+    fn alignment_per_axis() {
+        let positions: PlanarCrabPositions = "0:0,2:10,3:1,10:2".parse().unwrap();
+        assert_eq!(
+            positions.manhattan_alignment(),
+            Ok(PlanarAlignment {
+                target: Point { x: 2, y: 1 },
+                fuel: 11 + 11
+            })
+        );
+        let on_a_line: PlanarCrabPositions = "16,1,2,0,4,2,7,1,2,14".parse().unwrap();
+        assert_eq!(
+            on_a_line.optimal_alignment_per_axis(&Triangular),
+            Ok(PlanarAlignment {
+                target: Point { x: 5, y: 0 },
+                fuel: 168
+            })
+        );
+    }
+
+    #[test]
+    fn overflow_of_both_axes_together() {
+        let positions = PlanarCrabPositions {
+            points: vec![
+                Point {
+                    x: Position::MIN,
+                    y: Position::MIN,
+                },
+                Point {
+                    x: Position::MAX,
+                    y: Position::MAX,
+                },
+                Point {
+                    x: Position::MAX,
+                    y: Position::MAX,
+                },
+            ],
+        };
+        assert!(matches!(
+            positions.optimal_alignment_per_axis(&Quadratic),
+            Err(CrabError::PlanarFuelOverflow { target }) if target.x == target.y
+        ));
+    }
+
+    #[test]
+    fn geometric_median() {
+        let square: PlanarCrabPositions = "0:0,0:2,2:0,2:2".parse().unwrap();
+        let median = square.geometric_median().unwrap();
+        assert!((median.x - 1.0).abs() < 1e-6 && (median.y - 1.0).abs() < 1e-6);
+        assert!((median.distance - 4.0 * 2f64.sqrt()).abs() < 1e-6);
+
+        // With an angle of at least 120 degrees the median is that corner
+        let obtuse: PlanarCrabPositions = "0:0,10:0,-10:1".parse().unwrap();
+        let median = obtuse.geometric_median().unwrap();
+        assert_eq!((median.x, median.y), (0.0, 0.0));
+
+        // Several crabs at one position outweigh the others
+        let crowded: PlanarCrabPositions = "5:5,5:5,5:5,0:0,10:1,3:12".parse().unwrap();
+        let median = crowded.geometric_median().unwrap();
+        assert_eq!((median.x, median.y), (5.0, 5.0));
+
+        let empty: PlanarCrabPositions = "".parse().unwrap();
+        assert_eq!(empty.geometric_median(), Err(CrabError::NoCrabs));
+    }
+
+    #[test]
+    fn geometric_median_beats_grid_search() {
+        let positions: PlanarCrabPositions = "0:0,7:1,3:9,12:4,5:5,1:11".parse().unwrap();
+        let median = positions.geometric_median().unwrap();
+        let total = |x: f64, y: f64| {
+            positions
+                .points()
+                .iter()
+                .map(|point| euclidean((point.x as f64, point.y as f64), (x, y)))
+                .sum::<f64>()
+        };
+        for x in 0..=120 {
+            for y in 0..=120 {
+                assert!(median.distance <= total(x as f64 / 10.0, y as f64 / 10.0) + 1e-9);
+            }
+        }
+    }
+}
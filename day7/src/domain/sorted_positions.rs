@@ -0,0 +1,112 @@
+use crate::domain::{Alignment, Coefficients, CrabError, Fuel, Position};
+
+// Prefix sums over the sorted positions, so the fuel for a target takes one binary search.
+// Positions are stored relative to the smallest one, which keeps the sums small for tight fleets.
+pub struct SortedPositions {
+    reference: i128,
+    sorted: Vec<i128>,
+    sums: Vec<i128>,
+    // None when the sum of squares does not fit
+    squares: Option<Vec<i128>>,
+}
+
+impl SortedPositions {
+    pub fn new(positions: &[Position]) -> Self {
+        let mut sorted: Vec<i128> = positions.iter().map(|&position| position as i128).collect();
+        sorted.sort_unstable();
+        let reference = sorted.first().copied().unwrap_or(0);
+        sorted
+            .iter_mut()
+            .for_each(|position| *position -= reference);
+        let mut sums = vec![0];
+        let mut squares: Option<Vec<i128>> = Some(vec![0]);
+        for &position in &sorted {
+            sums.push(sums.last().unwrap() + position);
+            squares = squares.and_then(|mut squares| {
+                squares.push(
+                    squares
+                        .last()
+                        .unwrap()
+                        .checked_add(position.checked_mul(position)?)?,
+                );
+                Some(squares)
+            });
+        }
+        SortedPositions {
+            reference,
+            sorted,
+            sums,
+            squares,
+        }
+    }
+
+    pub fn fuel(&self, target: Position, coefficients: &Coefficients) -> Result<Fuel, CrabError> {
+        let target_offset = target as i128 - self.reference;
+        self.checked_fuel(target_offset, coefficients)
+            .or_else(|| self.fuel_per_crab(target_offset, coefficients))
+            .ok_or(CrabError::FuelOverflow { target })
+    }
+
+    // Expands the sum of (position - target)^2, so intermediate values can overflow before the fuel does
+    fn checked_fuel(&self, target: i128, coefficients: &Coefficients) -> Option<Fuel> {
+        let count = self.sorted.len() as i128;
+        let below = self.sorted.partition_point(|&position| position <= target);
+        let (sum_below, sum) = (self.sums[below], *self.sums.last().unwrap());
+        let distance = target * below as i128 - sum_below + (sum - sum_below)
+            - target * (count - below as i128);
+        let squared_distance = match coefficients.quadratic {
+            0 => 0,
+            _ => self
+                .squares
+                .as_ref()?
+                .last()
+                .unwrap()
+                .checked_sub(target.checked_mul(2)?.checked_mul(sum)?)?
+                .checked_add(count.checked_mul(target.checked_mul(target)?)?)?,
+        };
+        let fuel = coefficients
+            .linear
+            .checked_mul(distance)?
+            .checked_add(coefficients.quadratic.checked_mul(squared_distance)?)?
+            / coefficients.divisor;
+        Some(fuel as Fuel)
+    }
+
+    // Fallback for when the expanded sums overflow, linear in the number of crabs
+    fn fuel_per_crab(&self, target: i128, coefficients: &Coefficients) -> Option<Fuel> {
+        let (linear, quadratic) = (coefficients.linear as Fuel, coefficients.quadratic as Fuel);
+        let total = self.sorted.iter().try_fold(0, |total: Fuel, &position| {
+            let distance = position.abs_diff(target);
+            let squared = match quadratic {
+                0 => 0,
+                _ => quadratic.checked_mul(distance.checked_mul(distance)?)?,
+            };
+            let fuel = linear.checked_mul(distance)?.checked_add(squared)?;
+            total.checked_add(fuel)
+        })?;
+        Some(total / coefficients.divisor as Fuel)
+    }
+
+    // Binary search for the first target where the fuel stops decreasing
+    pub fn optimal_alignment(&self, coefficients: &Coefficients) -> Result<Alignment, CrabError> {
+        let (mut low, mut high) = match (self.sorted.first(), self.sorted.last()) {
+            (Some(&low), Some(&high)) => (
+                (low + self.reference) as Position,
+                (high + self.reference) as Position,
+            ),
+            _ => return Err(CrabError::NoCrabs),
+        };
+        while low < high {
+            let middle = ((low as i128 + high as i128).div_euclid(2)) as Position;
+            if self.fuel(middle + 1, coefficients)? < self.fuel(middle, coefficients)? {
+                low = middle + 1;
+            } else {
+                high = middle;
+            }
+        }
+        Ok(Alignment {
+            target: low,
+            fuel: self.fuel(low, coefficients)?,
+        })
+    }
+}
@@ -1,19 +1,42 @@
-use crate::domain::CrabPositions;
+pub use crate::domain::{
+    Alignment, Coefficients, CrabError, CrabPositions, Fuel, FuelCost, FuelCurve, GeometricMedian,
+    Linear, PlanarAlignment, PlanarCrabPositions, Point, Position, Quadratic, Triangular,
+};
 
 mod domain;
 
-pub fn calculate_part1(input: &str) -> i32 {
-    let crab_positions: CrabPositions = input.parse().unwrap();
-    crab_positions.required_fuel_to_reach_simple(crab_positions.median())
+pub fn calculate_part1(input: &str) -> Result<Fuel, CrabError> {
+    calculate_optimal_alignment(input, &Linear).map(|alignment| alignment.fuel)
 }
 
-pub fn calculate_part2(input: &str) -> i32 {
-    let crab_positions: CrabPositions = input.parse().unwrap();
+pub fn calculate_part2(input: &str) -> Result<Fuel, CrabError> {
+    calculate_optimal_alignment(input, &Triangular).map(|alignment| alignment.fuel)
+}
+
+pub fn calculate_optimal_alignment<C: FuelCost>(
+    input: &str,
+    cost: &C,
+) -> Result<Alignment, CrabError> {
+    let crab_positions: CrabPositions = input.parse()?;
+    crab_positions.optimal_alignment(cost)
+}
+
+pub fn calculate_fuel_curve<C: FuelCost>(input: &str, cost: &C) -> Result<FuelCurve, CrabError> {
+    let crab_positions: CrabPositions = input.parse()?;
+    crab_positions.fuel_curve(crab_positions.min()?..=crab_positions.max()?, cost)
+}
+
+pub fn calculate_planar_alignment<C: FuelCost>(
+    input: &str,
+    cost: &C,
+) -> Result<PlanarAlignment, CrabError> {
+    let crab_positions: PlanarCrabPositions = input.parse()?;
+    crab_positions.optimal_alignment_per_axis(cost)
+}
 
-    (crab_positions.min()..=crab_positions.max())
-        .map(|potential_target| crab_positions.required_fuel_to_reach_power(potential_target))
-        .min()
-        .unwrap_or(0)
+pub fn calculate_geometric_median(input: &str) -> Result<GeometricMedian, CrabError> {
+    let crab_positions: PlanarCrabPositions = input.parse()?;
+    crab_positions.geometric_median()
 }
 
 #[cfg(test)]
@@ -23,26 +46,96 @@ mod tests {
     #[test]
     fn test_calculate_1() {
         let distance = calculate_part1("16,1,2,0,4,2,7,1,2,14\n");
-        assert_eq!(distance, 37)
+        assert_eq!(distance, Ok(37))
     }
 
     #[test]
     fn test_real_input_1() {
         let input = include_str!("../input.txt");
         let distance = calculate_part1(input);
-        assert_eq!(distance, 336120)
+        assert_eq!(distance, Ok(336120))
     }
 
     #[test]
     fn test_calculate_part2() {
         let positions = "16, 1, 2, 0, 4, 2, 7, 1, 2, 14\n";
-        assert_eq!(calculate_part2(&positions), 168);
+        assert_eq!(calculate_part2(positions), Ok(168));
     }
 
     #[test]
     fn test_real_input_2() {
         let input = include_str!("../input.txt");
         let distance = calculate_part2(input);
-        assert_eq!(distance, 96864235)
+        assert_eq!(distance, Ok(96864235))
+    }
+
+    #[test]
+    fn test_fuel_curve_of_real_input() {
+        let curve = calculate_fuel_curve(include_str!("../input.txt"), &Triangular).unwrap();
+        assert_eq!(curve.minimum().map(|minimum| minimum.fuel), Some(96864235));
+        assert!(curve.to_ascii_plot(60, 10).ends_with("fuel 96864235\n"));
+    }
+
+    #[test]
+    fn test_real_input_equals_brute_force() {
+        let crab_positions: CrabPositions = include_str!("../input.txt").parse().unwrap();
+        assert_eq!(
+            crab_positions.optimal_alignment(&Linear),
+            crab_positions.optimal_alignment_by_brute_force(&Linear)
+        );
+        assert_eq!(
+            crab_positions.optimal_alignment(&Triangular),
+            crab_positions.optimal_alignment_by_brute_force(&Triangular)
+        );
+    }
+
+    #[test]
+    fn test_planar_real_input() {
+        let input = include_str!("../input.txt");
+        assert_eq!(
+            calculate_planar_alignment(input, &Linear).map(|alignment| alignment.fuel),
+            Ok(336120)
+        );
+        assert_eq!(
+            calculate_planar_alignment("0:0,4:4,4:0\n", &Triangular),
+            Ok(PlanarAlignment {
+                target: Point { x: 3, y: 1 },
+                fuel: (6 + 1 + 1) + (1 + 6 + 1)
+            })
+        );
+        let median = calculate_geometric_median("0:0,4:0,0:4,4:4\n").unwrap();
+        assert!((median.x - 2.0).abs() < 1e-6 && (median.y - 2.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_empty_input() {
+        assert_eq!(calculate_part1("\n"), Err(CrabError::NoCrabs));
+        assert_eq!(calculate_fuel_curve("\n", &Linear), Err(CrabError::NoCrabs));
+    }
+
+    #[test]
+    fn test_invalid_positions() {
+        assert_eq!(
+            calculate_part1("1,x,3\n"),
+            Err(CrabError::InvalidPosition(String::from("x")))
+        );
+        assert_eq!(
+            calculate_part2("1,2,99999999999999999999,3\n"),
+            Err(CrabError::InvalidPosition(String::from(
+                "99999999999999999999"
+            )))
+        );
+        assert_eq!(
+            calculate_fuel_curve("1:2,3\n", &Linear),
+            Err(CrabError::InvalidPosition(String::from("1:2")))
+        );
+        assert_eq!(
+            calculate_planar_alignment("1:2,3:x\n", &Linear),
+            Err(CrabError::InvalidPosition(String::from("3:x")))
+        );
+        assert_eq!(
+            calculate_geometric_median("1:2:3\n"),
+            Err(CrabError::InvalidPosition(String::from("1:2:3")))
+        );
     }
 }